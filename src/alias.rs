@@ -0,0 +1,140 @@
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::install::{get_named_toolchains, toolchain_dir};
+use crate::utils::get_govm_dir;
+use crate::version::{is_valid_toolchain_name, Toolchain};
+
+/// 保存别名的文件，内容为 别名 -> 版本号或工具链名称
+const ALIASES_FILE: &str = "aliases.json";
+
+fn aliases_path() -> Result<PathBuf> {
+    Ok(get_govm_dir()?.join(ALIASES_FILE))
+}
+
+/// 读取所有别名
+pub fn load_aliases() -> BTreeMap<String, String> {
+    aliases_path()
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_aliases(aliases: &BTreeMap<String, String>) -> Result<()> {
+    fs::write(aliases_path()?, serde_json::to_vec_pretty(aliases)?)?;
+    Ok(())
+}
+
+/// 将别名解析为版本号或工具链名称，不是别名时原样返回
+pub fn resolve(value: &str) -> String {
+    resolve_in(&load_aliases(), value)
+}
+
+fn resolve_in(aliases: &BTreeMap<String, String>, value: &str) -> String {
+    aliases.get(value.trim()).cloned().unwrap_or_else(|| value.to_string())
+}
+
+/// 指向某个工具链的所有别名
+pub fn aliases_for(toolchain: &Toolchain) -> Vec<String> {
+    aliases_pointing_to(&load_aliases(), toolchain)
+}
+
+fn aliases_pointing_to(aliases: &BTreeMap<String, String>, toolchain: &Toolchain) -> Vec<String> {
+    aliases
+        .iter()
+        .filter(|(_, target)| Toolchain::parse(target).ok().as_ref() == Some(toolchain))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// 设置别名，目标可以是尚未安装的版本（之后可以用 govm install <别名> 安装）
+pub fn set_alias(name: &str, target: &str) -> Result<()> {
+    if !is_valid_toolchain_name(name) {
+        return Err(anyhow!(
+            "Invalid alias name: {}. Use letters, digits, '.', '_', '-' or '+', and not a version number",
+            name
+        ));
+    }
+    if get_named_toolchains()?.iter().any(|n| n == name) {
+        return Err(anyhow!("{} is already the name of a custom toolchain", name));
+    }
+
+    let mut aliases = load_aliases();
+    if aliases.contains_key(target) {
+        return Err(anyhow!("{} is an alias; point {} at a version or toolchain name instead", target, name));
+    }
+    let toolchain = Toolchain::parse(target)?;
+    let target = toolchain.to_string();
+
+    let previous = aliases.insert(name.to_string(), target.clone());
+    save_aliases(&aliases)?;
+
+    match previous {
+        Some(old) if old != target => println!("✅ Alias {} now points to {} (was {})", name, target, old),
+        _ => println!("✅ Alias {} -> {}", name, target),
+    }
+    if !toolchain_dir(&toolchain)?.exists() {
+        println!("{}", format!("Go {} is not installed. Run 'govm install {}' to install it.", target, name).yellow());
+    }
+
+    Ok(())
+}
+
+/// 删除别名
+pub fn remove_alias(name: &str) -> Result<()> {
+    let mut aliases = load_aliases();
+    let target = aliases.remove(name).ok_or_else(|| anyhow!("Alias {} does not exist", name))?;
+    save_aliases(&aliases)?;
+    println!("✅ Removed alias {} (was {})", name, target);
+    Ok(())
+}
+
+/// 列出所有别名
+pub fn list_aliases() -> Result<()> {
+    let aliases = load_aliases();
+
+    if aliases.is_empty() {
+        println!("{}", "No aliases defined.".yellow());
+        println!("Run 'govm alias set <name> <version>' to create one.");
+        return Ok(());
+    }
+
+    println!("{}", "Aliases:".bold().green());
+    println!();
+
+    for (name, target) in &aliases {
+        let installed = Toolchain::parse(target)
+            .and_then(|t| toolchain_dir(&t))
+            .map(|d| d.exists())
+            .unwrap_or(false);
+        let status = if installed { "".normal() } else { "(not installed)".yellow() };
+        println!("  {:<16} -> {} {}", name.cyan(), target, status);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::GoVersion;
+
+    #[test]
+    fn test_resolve_aliases() {
+        let mut aliases = BTreeMap::new();
+        aliases.insert("prod".to_string(), "1.22.3".to_string());
+        aliases.insert("legacy-service".to_string(), "1.20.14".to_string());
+        aliases.insert("canary".to_string(), "1.22.3".to_string());
+
+        assert_eq!(resolve_in(&aliases, "prod"), "1.22.3");
+        assert_eq!(resolve_in(&aliases, "1.21.5"), "1.21.5");
+        assert_eq!(
+            aliases_pointing_to(&aliases, &Toolchain::Release(GoVersion::new(1, 22, 3))),
+            ["canary", "prod"]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+use crate::config::http_client;
+use crate::dedupe;
+use crate::download::{download_source, Platform};
+use crate::install::{extract_archive, get_installed_versions, toolchain_dir, version_dir};
+use crate::utils::{format_size, dir_size, get_versions_dir};
+use crate::version::{GoVersion, Toolchain};
+
+/// 记录源码构建信息的文件
+pub const SOURCE_MARKER: &str = ".govm-source";
+
+/// 从源码构建的版本的来源信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// 源码包文件名或本地源码目录
+    pub source: String,
+    /// git 提交（本地源码目录）或发行版本号（源码包）
+    pub revision: String,
+    /// 用于自举的 Go 版本
+    pub bootstrap: String,
+}
+
+/// 读取版本目录中的构建信息，不是从源码构建的版本返回 None
+pub fn read_build_info(version_dir: &Path) -> Option<BuildInfo> {
+    fs::read_to_string(version_dir.join(SOURCE_MARKER))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+}
+
+/// 构建 Go 1.minor 所需的最低自举版本，Go 1.4 及更早的版本用 C 编译器构建
+/// 规则见 https://go.dev/doc/install/source#bootstrapFromSource：
+/// 1.5-1.19 需要 1.4，1.20-1.21 需要 1.17.13，之后 Go 1.N 需要 1.M.6（M 为 N-2 向下取偶数）
+fn bootstrap_requirement(minor: u32) -> Option<GoVersion> {
+    match minor {
+        0..=4 => None,
+        5..=19 => Some(GoVersion::new(1, 4, 0)),
+        20..=21 => Some(GoVersion::new(1, 17, 13)),
+        _ => Some(GoVersion::new(1, (minor - 2) & !1, 6)),
+    }
+}
+
+/// 从已安装的版本中选择自举版本：满足要求的最新版本
+fn select_bootstrap(installed: &[GoVersion], required: &GoVersion) -> Option<GoVersion> {
+    installed.iter().filter(|v| *v >= required).max().cloned()
+}
+
+/// 从源码目录的 VERSION 文件读取版本，开发分支没有该文件或内容为 devel
+fn read_tree_version(root: &Path) -> Option<GoVersion> {
+    let content = fs::read_to_string(root.join("VERSION")).ok()?;
+    GoVersion::parse(content.lines().next()?).ok()
+}
+
+/// 从 src/internal/goversion/goversion.go 读取次版本号（const Version = 23）
+fn read_tree_minor(root: &Path) -> Option<u32> {
+    let content = fs::read_to_string(root.join("src/internal/goversion/goversion.go")).ok()?;
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("const Version = "))
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// 本地源码目录的 git 提交，有未提交的修改时加上 -dirty
+fn git_revision(root: &Path) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(root).args(["rev-parse", "HEAD"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut revision = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let status = Command::new("git").arg("-C").arg(root).args(["status", "--porcelain"]).output().ok()?;
+    if !status.stdout.is_empty() {
+        revision.push_str("-dirty");
+    }
+    Some(revision)
+}
+
+/// 复制源码目录，跳过 .git 以及之前在原地构建留下的 bin 和 pkg
+fn copy_source_tree(src: &Path, dst: &Path) -> Result<()> {
+    let walker = WalkDir::new(src).into_iter().filter_entry(|e| {
+        e.depth() != 1 || !matches!(e.file_name().to_str(), Some(".git") | Some("bin") | Some("pkg"))
+    });
+
+    for entry in walker {
+        let entry = entry?;
+        let dest_path = dst.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest_path)?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &dest_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 在源码树中运行 make.bash（Windows 上为 make.bat）
+fn run_make(goroot: &Path, bootstrap: Option<&Path>) -> Result<()> {
+    let src_dir = goroot.join("src");
+    let mut command = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", "make.bat"]);
+        c
+    } else {
+        let mut c = Command::new("bash");
+        c.arg("make.bash");
+        c
+    };
+
+    command
+        .current_dir(&src_dir)
+        .env_remove("GOROOT")
+        .env_remove("GOFLAGS")
+        // 避免自举用的 go 命令根据 go.mod 切换到其他工具链
+        .env("GOTOOLCHAIN", "local");
+    if let Some(bootstrap) = bootstrap {
+        command.env("GOROOT_BOOTSTRAP", bootstrap);
+    }
+
+    let status = command.status().context("Failed to run make.bash")?;
+    if !status.success() {
+        return Err(anyhow!("make.bash failed ({})", status));
+    }
+
+    Ok(())
+}
+
+/// 构建所用的源码
+enum BuildSource {
+    /// 缓存中的官方源码包
+    Archive(PathBuf),
+    /// 本地源码目录
+    Tree(PathBuf),
+}
+
+/// 从源码构建 Go 并安装为受管理的版本
+/// `source` 为版本选择器（1.22.3、1.22、latest）或本地源码目录（例如 git checkout）
+/// `name` 指定安装的版本号或自定义工具链名称，源码目录中没有 VERSION 文件时必须指定
+pub async fn build_version(source: &str, name: Option<&str>) -> Result<()> {
+    let name = name.map(Toolchain::parse).transpose()?;
+    let host = Platform::host();
+    let local = Path::new(source);
+
+    let (build_source, target, minor, origin, revision) = if local.is_dir() {
+        let root = local.canonicalize()?;
+        if !root.join("src").join("make.bash").is_file() {
+            return Err(anyhow!("{} does not look like a Go source tree (src/make.bash not found)", root.display()));
+        }
+        let tree_version = read_tree_version(&root);
+        let target = name.or_else(|| tree_version.clone().map(Toolchain::Release)).ok_or_else(|| {
+            anyhow!("{} has no release VERSION file; pass --name (for example --name tip) to name the build", root.display())
+        })?;
+        let minor = tree_version
+            .map(|v| v.minor)
+            .or_else(|| read_tree_minor(&root))
+            .or(match &target {
+                Toolchain::Release(v) => Some(v.minor),
+                Toolchain::Named(_) => None,
+            })
+            .ok_or_else(|| anyhow!("Cannot determine the Go version of {}", root.display()))?;
+        let revision = git_revision(&root).unwrap_or_else(|| "unknown".to_string());
+        let origin = root.display().to_string();
+        (BuildSource::Tree(root), target, minor, origin, revision)
+    } else {
+        let (release, archive) = download_source(&http_client()?, source).await?;
+        let origin = archive.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let revision = release.to_download_string();
+        let target = name.unwrap_or_else(|| Toolchain::Release(release.clone()));
+        (BuildSource::Archive(archive), target, release.minor, origin, revision)
+    };
+
+    let dest = toolchain_dir(&target)?;
+    if fs::symlink_metadata(&dest).is_ok() {
+        return Err(anyhow!("Go {} is already installed. Uninstall it first or pass --name", target));
+    }
+
+    let bootstrap = match bootstrap_requirement(minor) {
+        Some(required) => Some(select_bootstrap(&get_installed_versions()?, &required).ok_or_else(|| {
+            anyhow!(
+                "Building Go 1.{} requires Go {} or newer for bootstrap. Run 'govm install {}' first",
+                minor,
+                required,
+                required
+            )
+        })?),
+        None => None,
+    };
+    let bootstrap_dir = bootstrap.as_ref().map(|v| version_dir(v, &host)).transpose()?;
+
+    println!("{}", format!("Building Go {} from {}", target, origin).blue());
+    if let Some(ref bootstrap) = bootstrap {
+        println!("Bootstrap toolchain: Go {}", bootstrap);
+    }
+
+    // 在 versions 目录中构建，成功后重命名即可，失败时删除
+    let build_dir = get_versions_dir()?.join(format!(".build-{}", target.dir_name()));
+    if build_dir.exists() {
+        fs::remove_dir_all(&build_dir)?;
+    }
+    fs::create_dir_all(&build_dir)?;
+
+    let result = (|| -> Result<()> {
+        match &build_source {
+            BuildSource::Archive(archive) => extract_archive(archive, &build_dir)?,
+            BuildSource::Tree(root) => {
+                copy_source_tree(root, &build_dir)?;
+                // 复制时跳过了 .git，make.bash 需要 VERSION 文件来确定版本字符串
+                if read_tree_version(&build_dir).is_none() {
+                    let short = revision.get(..12).unwrap_or(&revision);
+                    fs::write(build_dir.join("VERSION"), format!("devel go1.{}-{}\n", minor, short))?;
+                }
+            }
+        }
+
+        run_make(&build_dir, bootstrap_dir.as_deref())?;
+
+        // 构建缓存只在构建过程中使用
+        let _ = fs::remove_dir_all(build_dir.join("pkg").join("obj"));
+
+        let info = BuildInfo {
+            source: origin.clone(),
+            revision: revision.clone(),
+            bootstrap: bootstrap.as_ref().map(|v| v.to_string()).unwrap_or_default(),
+        };
+        fs::write(build_dir.join(SOURCE_MARKER), serde_json::to_vec_pretty(&info)?)?;
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&build_dir);
+        return Err(e);
+    }
+
+    fs::rename(&build_dir, &dest)?;
+
+    if dedupe::is_enabled() {
+        let stats = dedupe::dedupe_dir(&dest)?;
+        println!("Linked {} files shared with other versions ({} saved)", stats.linked, format_size(stats.saved));
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!("✅ Go {} built and installed to {} ({})", target, dest.display(), format_size(dir_size(&dest))).green()
+    );
+    println!("Source revision: {}", revision);
+    println!("Run 'govm use {}' to switch to it", target);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_requirement() {
+        assert_eq!(bootstrap_requirement(4), None);
+        assert_eq!(bootstrap_requirement(19), Some(GoVersion::new(1, 4, 0)));
+        assert_eq!(bootstrap_requirement(21), Some(GoVersion::new(1, 17, 13)));
+        assert_eq!(bootstrap_requirement(22), Some(GoVersion::new(1, 20, 6)));
+        assert_eq!(bootstrap_requirement(23), Some(GoVersion::new(1, 20, 6)));
+        assert_eq!(bootstrap_requirement(24), Some(GoVersion::new(1, 22, 6)));
+        assert_eq!(bootstrap_requirement(26), Some(GoVersion::new(1, 24, 6)));
+    }
+
+    #[test]
+    fn test_select_bootstrap() {
+        let installed = [GoVersion::new(1, 22, 3), GoVersion::new(1, 21, 5), GoVersion::new(1, 20, 4)];
+        assert_eq!(select_bootstrap(&installed, &GoVersion::new(1, 20, 6)), Some(GoVersion::new(1, 22, 3)));
+        assert_eq!(select_bootstrap(&installed, &GoVersion::new(1, 22, 6)), None);
+    }
+}
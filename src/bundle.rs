@@ -0,0 +1,245 @@
+use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::http_client;
+use crate::checksum::{sha256_file, verify_sha256};
+use crate::download::{fetch_to_cache, get_go_archive_name, parse_archive_name, resolve_archive, Platform};
+use crate::install::{install_version, is_version_installed, Profile, Scope};
+use crate::utils::{format_size, get_cache_dir};
+use crate::version::GoVersion;
+
+/// 离线包格式版本
+const BUNDLE_FORMAT: u32 = 1;
+
+/// 离线包内的清单文件名
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// 离线包内存放压缩包的目录
+const ARCHIVES_DIR: &str = "archives";
+
+/// 离线包清单
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format: u32,
+    pub created_by: String,
+    pub toolchains: Vec<BundleEntry>,
+}
+
+/// 离线包中的一个工具链
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub version: GoVersion,
+    pub os: String,
+    pub arch: String,
+    pub file: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// 将多个版本和平台的压缩包打包成一个离线包，未指定平台时使用当前平台
+/// 缓存中没有或未通过校验的压缩包会先下载到缓存
+pub async fn create_bundle(output: &Path, versions: &[GoVersion], platforms: &[Platform]) -> Result<()> {
+    let cache_dir = get_cache_dir()?;
+    let client = http_client()?;
+    let host = [Platform::host()];
+    let platforms = if platforms.is_empty() { &host[..] } else { platforms };
+    let mut entries = Vec::new();
+
+    for version in versions {
+        for platform in platforms {
+            let file = resolve_archive(&client, version, &platform.os, &platform.arch).await?;
+            let archive_path = fetch_to_cache(&client, &file, &cache_dir).await?;
+
+            let (_, os, arch) = parse_archive_name(&file.filename)
+                .ok_or_else(|| anyhow!("Unrecognized archive name: {}", file.filename))?;
+
+            entries.push(BundleEntry {
+                version: version.clone(),
+                os,
+                arch,
+                size: fs::metadata(&archive_path)?.len(),
+                sha256: if file.sha256.is_empty() { sha256_file(&archive_path)? } else { file.sha256.to_lowercase() },
+                file: file.filename,
+            });
+        }
+    }
+
+    let manifest = BundleManifest {
+        format: BUNDLE_FORMAT,
+        created_by: format!("govm {}", env!("CARGO_PKG_VERSION")),
+        toolchains: entries,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+
+    // 压缩包本身已经压缩，离线包使用不压缩的 tar
+    let file = fs::File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut builder = tar::Builder::new(file);
+
+    // 清单放在最前面，安装时可以先读取清单
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_NAME, manifest_json.as_slice())?;
+
+    for entry in &manifest.toolchains {
+        let archive_path = cache_dir.join(&entry.file);
+        builder.append_path_with_name(&archive_path, format!("{}/{}", ARCHIVES_DIR, entry.file))?;
+        println!("  + {} ({}/{}, {})", entry.file, entry.os, entry.arch, format_size(entry.size));
+    }
+
+    builder.into_inner()?.sync_all()?;
+
+    println!(
+        "{}",
+        format!(
+            "✅ Created bundle {} with {} toolchain(s) ({})",
+            output.display(),
+            manifest.toolchains.len(),
+            format_size(fs::metadata(output)?.len())
+        )
+        .green()
+    );
+
+    Ok(())
+}
+
+/// 清单中的文件名必须是单纯的文件名，不能包含路径分隔符或 ..
+fn is_plain_file_name(name: &str) -> bool {
+    let mut components = Path::new(name).components();
+    matches!(components.next(), Some(Component::Normal(_)))
+        && components.next().is_none()
+        && !name.contains(['/', '\\'])
+}
+
+/// 验证并安装离线包中的所有工具链
+/// 压缩包先解压为 .partial 文件，校验通过后才替换缓存中的同名文件，之后可以直接重新安装
+pub fn install_bundle(bundle_path: &Path) -> Result<()> {
+    let cache_dir = get_cache_dir()?;
+    let file = fs::File::open(bundle_path)
+        .with_context(|| format!("Failed to open {}", bundle_path.display()))?;
+    let mut archive = tar::Archive::new(file);
+
+    let mut manifest: Option<BundleManifest> = None;
+    let mut partials: Vec<PathBuf> = Vec::new();
+
+    let result = (|| -> Result<()> {
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+
+            if path == Path::new(MANIFEST_NAME) {
+                let mut content = String::new();
+                entry.read_to_string(&mut content)?;
+                let parsed: BundleManifest = serde_json::from_str(&content)
+                    .context("Invalid bundle manifest")?;
+                if parsed.format != BUNDLE_FORMAT {
+                    return Err(anyhow!("Unsupported bundle format version {}", parsed.format));
+                }
+                if let Some(bad) = parsed.toolchains.iter().find(|t| !is_plain_file_name(&t.file)) {
+                    return Err(anyhow!("Invalid bundle manifest: {:?} is not a plain file name", bad.file));
+                }
+                manifest = Some(parsed);
+                continue;
+            }
+
+            let manifest = manifest.as_ref()
+                .ok_or_else(|| anyhow!("Invalid bundle: {} must come first", MANIFEST_NAME))?;
+
+            // 只接受清单中列出的文件，防止写到缓存目录之外
+            let name = match (path.parent(), path.file_name()) {
+                (Some(parent), Some(name)) if parent == Path::new(ARCHIVES_DIR) => {
+                    name.to_string_lossy().to_string()
+                }
+                _ => continue,
+            };
+            if !manifest.toolchains.iter().any(|t| t.file == name) {
+                continue;
+            }
+
+            let partial = cache_dir.join(format!("{}.partial", name));
+            partials.push(partial.clone());
+            entry.unpack(&partial)?;
+        }
+
+        let manifest = manifest.as_ref().ok_or_else(|| anyhow!("Invalid bundle: missing {}", MANIFEST_NAME))?;
+
+        // 全部校验通过后再替换缓存中的文件，损坏或被篡改的离线包不会覆盖已有的压缩包
+        for entry in &manifest.toolchains {
+            let partial = cache_dir.join(format!("{}.partial", entry.file));
+            if !partial.is_file() {
+                return Err(anyhow!("Invalid bundle: {} is missing", entry.file));
+            }
+            verify_sha256(&partial, &entry.sha256)
+                .with_context(|| format!("Go {} ({}/{})", entry.version, entry.os, entry.arch))?;
+        }
+        for entry in &manifest.toolchains {
+            fs::rename(cache_dir.join(format!("{}.partial", entry.file)), cache_dir.join(&entry.file))?;
+        }
+        Ok(())
+    })();
+
+    if let Err(e) = result {
+        for partial in &partials {
+            let _ = fs::remove_file(partial);
+        }
+        return Err(e);
+    }
+
+    let manifest = manifest.ok_or_else(|| anyhow!("Invalid bundle: missing {}", MANIFEST_NAME))?;
+
+    let mut installed = 0;
+    for entry in &manifest.toolchains {
+        let archive_path = cache_dir.join(&entry.file);
+        println!("{}", format!("Go {} ({}/{})", entry.version, entry.os, entry.arch).bold());
+        println!("  ✓ Checksum verified");
+
+        if entry.file != get_go_archive_name(&entry.version)? {
+            println!("  Skipped: built for {}/{}, kept in cache", entry.os, entry.arch);
+            continue;
+        }
+
+        if is_version_installed(&entry.version, &Platform::host())? {
+            println!("  Skipped: already installed");
+            continue;
+        }
+
+        install_version(&archive_path, &entry.version, &Platform::host(), Profile::Full, Scope::User)?;
+        installed += 1;
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "✅ Installed {} of {} toolchain(s) from {}",
+            installed,
+            manifest.toolchains.len(),
+            bundle_path.display()
+        )
+        .green()
+        .bold()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_plain_file_name() {
+        assert!(is_plain_file_name("go1.22.3.linux-amd64.tar.gz"));
+        assert!(!is_plain_file_name("../config.toml"));
+        assert!(!is_plain_file_name("archives/go1.22.3.linux-amd64.tar.gz"));
+        assert!(!is_plain_file_name("/etc/passwd"));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name(""));
+    }
+}
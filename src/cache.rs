@@ -0,0 +1,419 @@
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::http_client;
+use crate::checksum::sha256_file;
+use crate::download::{fetch_releases, parse_archive_name, Platform};
+use crate::goproxy::{ToolchainModule, MODULE_CACHE_DIR};
+use crate::install::is_version_installed;
+use crate::utils::{format_age, format_size, get_cache_dir};
+use crate::version::GoVersion;
+
+/// 校验记录文件，记录上次通过校验时文件的大小和修改时间
+const VERIFIED_FILE: &str = ".verified.json";
+
+/// 缓存条目的类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheKind {
+    /// 官方压缩包
+    Archive { version: GoVersion, os: String, arch: String },
+    /// 源码包
+    Source(GoVersion),
+    /// govm serve 生成的工具链模块 zip
+    Module(ToolchainModule),
+    /// 未完成的下载或打包
+    Partial,
+}
+
+/// 缓存中的一个文件
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// 相对缓存目录的名称
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: CacheKind,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl CacheEntry {
+    fn version(&self) -> Option<&GoVersion> {
+        match &self.kind {
+            CacheKind::Archive { version, .. } => Some(version),
+            CacheKind::Source(version) => Some(version),
+            CacheKind::Module(module) => Some(&module.version),
+            CacheKind::Partial => None,
+        }
+    }
+
+    fn platform(&self) -> String {
+        match &self.kind {
+            CacheKind::Archive { os, arch, .. } => format!("{}/{}", os, arch),
+            CacheKind::Source(_) => "source".to_string(),
+            CacheKind::Module(module) => format!("{}/{}", module.os, module.arch),
+            CacheKind::Partial => "-".to_string(),
+        }
+    }
+
+    fn age(&self) -> Duration {
+        SystemTime::now().duration_since(self.modified).unwrap_or_default()
+    }
+
+    /// 是否属于已安装的版本（按压缩包或模块对应的平台检查，源码包不算）
+    fn is_installed(&self) -> bool {
+        let (version, platform) = match &self.kind {
+            CacheKind::Archive { version, os, arch } => (version, Platform::new(os, arch)),
+            CacheKind::Module(module) => (&module.version, Platform::new(&module.os, &module.arch)),
+            CacheKind::Source(_) | CacheKind::Partial => return false,
+        };
+        is_version_installed(version, &platform).unwrap_or(false)
+    }
+}
+
+/// 校验记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VerifiedRecord {
+    sha256: String,
+    size: u64,
+    modified: u64,
+}
+
+impl VerifiedRecord {
+    fn matches(&self, entry: &CacheEntry) -> bool {
+        self.size == entry.size && self.modified == unix_secs(entry.modified)
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+fn load_verified(cache_dir: &Path) -> HashMap<String, VerifiedRecord> {
+    fs::read_to_string(cache_dir.join(VERIFIED_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+fn save_verified(cache_dir: &Path, records: &HashMap<String, VerifiedRecord>) -> Result<()> {
+    fs::write(cache_dir.join(VERIFIED_FILE), serde_json::to_vec_pretty(records)?)?;
+    Ok(())
+}
+
+/// 根据文件名判断缓存条目的类型，无法识别的文件返回 None
+fn classify(name: &str) -> Option<CacheKind> {
+    if name.ends_with(".download") || name.ends_with(".partial") {
+        return Some(CacheKind::Partial);
+    }
+
+    if let Some(module_name) = name.strip_prefix(&format!("{}/", MODULE_CACHE_DIR)) {
+        let module_version = module_name.strip_suffix(".zip")?;
+        return ToolchainModule::parse(module_version).map(CacheKind::Module);
+    }
+
+    if let Some(version) = name.strip_suffix(".src.tar.gz") {
+        return GoVersion::parse(version).ok().map(CacheKind::Source);
+    }
+
+    let (version, os, arch) = parse_archive_name(name)?;
+    Some(CacheKind::Archive { version, os, arch })
+}
+
+/// 收集缓存中的所有条目，按修改时间从旧到新排序
+pub fn cache_entries(cache_dir: &Path) -> Result<Vec<CacheEntry>> {
+    let mut names = Vec::new();
+
+    for entry in fs::read_dir(cache_dir)?.flatten() {
+        names.push(entry.file_name().to_string_lossy().to_string());
+    }
+    if let Ok(modules) = fs::read_dir(cache_dir.join(MODULE_CACHE_DIR)) {
+        for entry in modules.flatten() {
+            names.push(format!("{}/{}", MODULE_CACHE_DIR, entry.file_name().to_string_lossy()));
+        }
+    }
+
+    let mut entries = Vec::new();
+    for name in names {
+        let kind = match classify(&name) {
+            Some(kind) => kind,
+            None => continue,
+        };
+        let path = cache_dir.join(&name);
+        let metadata = match fs::metadata(&path) {
+            Ok(m) if m.is_file() => m,
+            _ => continue,
+        };
+
+        entries.push(CacheEntry {
+            name,
+            path,
+            kind,
+            size: metadata.len(),
+            modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+        });
+    }
+
+    entries.sort_by(|a, b| a.modified.cmp(&b.modified).then_with(|| a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// 列出缓存内容
+pub fn list_cache() -> Result<()> {
+    let cache_dir = get_cache_dir()?;
+    let entries = cache_entries(&cache_dir)?;
+
+    if entries.is_empty() {
+        println!("{}", "Download cache is empty.".yellow());
+        return Ok(());
+    }
+
+    let verified = load_verified(&cache_dir);
+    println!("{}", format!("Download cache ({}):", cache_dir.display()).bold().green());
+    println!();
+    println!(
+        "  {:<10} {:<15} {:>10} {:>6}  STATUS",
+        "VERSION", "PLATFORM", "SIZE", "AGE"
+    );
+
+    let mut total = 0;
+    for entry in entries.iter().rev() {
+        total += entry.size;
+
+        let version = entry.version().map(|v| v.to_string()).unwrap_or_else(|| "-".to_string());
+        let version_str = format!("{:<10}", version);
+        let mut statuses = Vec::new();
+        match &entry.kind {
+            CacheKind::Archive { .. } | CacheKind::Source(_) => match verified.get(&entry.name) {
+                Some(record) if record.matches(entry) => statuses.push("verified".green()),
+                _ => statuses.push("unverified".yellow()),
+            },
+            CacheKind::Module(_) => statuses.push("toolchain module".dimmed()),
+            CacheKind::Partial => statuses.push("incomplete".red()),
+        }
+        if entry.is_installed() {
+            statuses.push("installed".blue());
+        }
+
+        println!(
+            "  {} {:<15} {:>10} {:>6}  {}",
+            version_str.cyan(),
+            entry.platform(),
+            format_size(entry.size),
+            format_age(entry.age()),
+            statuses.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    println!();
+    println!("{} file(s), {} total", entries.len(), format_size(total));
+
+    Ok(())
+}
+
+/// 使用官方发布信息中的 sha256 校验缓存中的压缩包
+pub async fn verify_cache(delete_invalid: bool) -> Result<()> {
+    let cache_dir = get_cache_dir()?;
+    let entries = cache_entries(&cache_dir)?;
+
+    println!("{}", "Fetching release checksums...".blue());
+    let client = http_client()?;
+    let expected: HashMap<String, String> = fetch_releases(&client)
+        .await?
+        .into_iter()
+        .flat_map(|r| r.files)
+        .filter(|f| !f.sha256.is_empty())
+        .map(|f| (f.filename, f.sha256.to_lowercase()))
+        .collect();
+
+    let mut records = load_verified(&cache_dir);
+    let mut ok = 0;
+    let mut failed = 0;
+
+    for entry in &entries {
+        match entry.kind {
+            CacheKind::Archive { .. } | CacheKind::Source(_) => {}
+            CacheKind::Partial => {
+                println!("  {} {} (incomplete download)", "?".yellow(), entry.name);
+                continue;
+            }
+            CacheKind::Module(_) => continue,
+        }
+
+        let expected = match expected.get(&entry.name) {
+            Some(e) => e,
+            None => {
+                println!("  {} {} (not in the release list)", "?".yellow(), entry.name);
+                continue;
+            }
+        };
+
+        let actual = sha256_file(&entry.path)?;
+        if &actual == expected {
+            println!("  {} {}", "✓".green(), entry.name);
+            records.insert(
+                entry.name.clone(),
+                VerifiedRecord { sha256: actual, size: entry.size, modified: unix_secs(entry.modified) },
+            );
+            ok += 1;
+            continue;
+        }
+
+        records.remove(&entry.name);
+        if delete_invalid {
+            fs::remove_file(&entry.path)?;
+            println!("  {} {} (checksum mismatch, deleted)", "✗".red(), entry.name);
+        } else {
+            println!("  {} {} (checksum mismatch)", "✗".red(), entry.name);
+            println!("      expected: {}", expected);
+            println!("      actual:   {}", actual);
+            failed += 1;
+        }
+    }
+
+    save_verified(&cache_dir, &records)?;
+
+    println!();
+    if failed > 0 {
+        return Err(anyhow!(
+            "{} cached archive(s) failed verification. Run 'govm cache verify --delete-invalid' to remove them.",
+            failed
+        ));
+    }
+    println!("{}", format!("✅ {} archive(s) verified", ok).green());
+
+    Ok(())
+}
+
+/// 选出需要清理的条目
+/// 先清理超过 older_than 的条目，再从最旧的开始清理直到总大小不超过 max_size
+fn select_prunable(
+    entries: &[CacheEntry],
+    older_than: Option<Duration>,
+    max_size: Option<u64>,
+    is_protected: impl Fn(&CacheEntry) -> bool,
+) -> Vec<&CacheEntry> {
+    let mut selected: Vec<bool> = entries
+        .iter()
+        .map(|e| !is_protected(e) && older_than.is_some_and(|limit| e.age() > limit))
+        .collect();
+
+    if let Some(max_size) = max_size {
+        let mut total: u64 = entries
+            .iter()
+            .zip(&selected)
+            .filter(|(_, s)| !**s)
+            .map(|(e, _)| e.size)
+            .sum();
+
+        // entries 已按修改时间从旧到新排序
+        for (entry, selected) in entries.iter().zip(selected.iter_mut()) {
+            if total <= max_size {
+                break;
+            }
+            if *selected || is_protected(entry) {
+                continue;
+            }
+            *selected = true;
+            total -= entry.size;
+        }
+    }
+
+    entries.iter().zip(selected).filter(|(_, s)| *s).map(|(e, _)| e).collect()
+}
+
+/// 按时间和总大小清理缓存
+pub fn prune_cache(
+    older_than: Option<Duration>,
+    max_size: Option<u64>,
+    keep_installed: bool,
+    dry_run: bool,
+) -> Result<()> {
+    if older_than.is_none() && max_size.is_none() {
+        return Err(anyhow!("Specify --older-than and/or --max-size. Use 'govm clean' to empty the cache."));
+    }
+
+    let cache_dir = get_cache_dir()?;
+    let entries = cache_entries(&cache_dir)?;
+    let prunable = select_prunable(&entries, older_than, max_size, |e| keep_installed && e.is_installed());
+
+    if prunable.is_empty() {
+        println!("Nothing to prune.");
+        return Ok(());
+    }
+
+    let mut records = load_verified(&cache_dir);
+    let mut reclaimed = 0;
+
+    for entry in &prunable {
+        if dry_run {
+            println!("  Would remove {} ({}, {} old)", entry.name, format_size(entry.size), format_age(entry.age()));
+        } else {
+            fs::remove_file(&entry.path)?;
+            records.remove(&entry.name);
+            println!("  Removed {} ({})", entry.name, format_size(entry.size));
+        }
+        reclaimed += entry.size;
+    }
+
+    println!();
+    if dry_run {
+        println!("Would reclaim {} from {} file(s)", format_size(reclaimed), prunable.len());
+    } else {
+        save_verified(&cache_dir, &records)?;
+        println!(
+            "{}",
+            format!("✅ Reclaimed {} from {} file(s)", format_size(reclaimed), prunable.len()).green()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, size: u64, days_old: u64) -> CacheEntry {
+        CacheEntry {
+            name: name.to_string(),
+            path: PathBuf::from(name),
+            kind: classify(name).unwrap(),
+            size,
+            modified: SystemTime::now() - Duration::from_secs(days_old * 86400),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert!(matches!(classify("go1.21.5.linux-amd64.tar.gz"), Some(CacheKind::Archive { .. })));
+        assert_eq!(classify("go1.21.5.linux-amd64.tar.gz.download"), Some(CacheKind::Partial));
+        assert!(matches!(
+            classify("toolchain-modules/v0.0.1-go1.21.5.linux-amd64.zip"),
+            Some(CacheKind::Module(_))
+        ));
+        assert_eq!(classify("go1.21.5.src.tar.gz"), Some(CacheKind::Source(GoVersion::new(1, 21, 5))));
+        assert_eq!(classify(".verified.json"), None);
+    }
+
+    #[test]
+    fn test_select_prunable() {
+        let entries = vec![
+            entry("go1.20.1.linux-amd64.tar.gz", 100, 60),
+            entry("go1.21.5.linux-amd64.tar.gz", 100, 40),
+            entry("go1.22.3.linux-amd64.tar.gz", 100, 1),
+        ];
+        let names = |selected: Vec<&CacheEntry>| selected.iter().map(|e| e.name.clone()).collect::<Vec<_>>();
+
+        let old = select_prunable(&entries, Some(Duration::from_secs(30 * 86400)), None, |_| false);
+        assert_eq!(names(old), ["go1.20.1.linux-amd64.tar.gz", "go1.21.5.linux-amd64.tar.gz"]);
+
+        let protected = |e: &CacheEntry| e.name.starts_with("go1.20.1");
+        let sized = select_prunable(&entries, None, Some(150), protected);
+        assert_eq!(names(sized), ["go1.21.5.linux-amd64.tar.gz", "go1.22.3.linux-amd64.tar.gz"]);
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{Result, anyhow, Context};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// 计算文件的 SHA256（小写十六进制）
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// 解析 sha256sum 格式的校验清单
+/// 每行格式: "<sha256>  <文件名>"，文件名前可能带 '*'（二进制模式）
+pub fn parse_sha256sums(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (hash, name) = line.split_once(char::is_whitespace)?;
+            let name = name.trim().trim_start_matches('*');
+            let name = Path::new(name).file_name()?.to_string_lossy().to_string();
+            Some((name, hash.to_lowercase()))
+        })
+        .collect()
+}
+
+/// 读取校验清单文件
+pub fn read_sha256sums(path: &Path) -> Result<HashMap<String, String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read checksum manifest {}", path.display()))?;
+    Ok(parse_sha256sums(&content))
+}
+
+/// 写入 sha256sum 格式的校验清单，按文件名排序
+pub fn write_sha256sums(path: &Path, manifest: &HashMap<String, String>) -> Result<()> {
+    let mut names: Vec<&String> = manifest.keys().collect();
+    names.sort();
+
+    let content: String = names
+        .into_iter()
+        .map(|name| format!("{}  {}\n", manifest[name], name))
+        .collect();
+
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// 校验文件的 SHA256 是否与期望值一致
+pub fn verify_sha256(path: &Path, expected: &str) -> Result<()> {
+    let actual = sha256_file(path)?;
+
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(anyhow!(
+            "Checksum mismatch for {}\n  expected: {}\n  actual:   {}",
+            path.display(),
+            expected,
+            actual
+        ));
+    }
+
+    Ok(())
+}
+
+/// 按文件名在清单中查找并校验
+pub fn verify_against_manifest(path: &Path, manifest: &HashMap<String, String>) -> Result<()> {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let expected = manifest
+        .get(&name)
+        .ok_or_else(|| anyhow!("No checksum for {} in the manifest", name))?;
+
+    verify_sha256(path, expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sha256sums() {
+        let content = "# comment\nABCDEF  go1.21.5.linux-amd64.tar.gz\n123456 *dist/go1.21.5.windows-amd64.zip\n";
+        let manifest = parse_sha256sums(content);
+        assert_eq!(manifest.get("go1.21.5.linux-amd64.tar.gz").map(String::as_str), Some("abcdef"));
+        assert_eq!(manifest.get("go1.21.5.windows-amd64.zip").map(String::as_str), Some("123456"));
+        assert_eq!(manifest.len(), 2);
+    }
+}
@@ -1,4 +1,9 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+use crate::download::{DownloadKind, Platform};
+use crate::install::Profile;
+use crate::system_go::{ImportMode, Manager};
 
 #[derive(Parser, Debug)]
 #[command(name = "govm")]
@@ -19,20 +24,100 @@ pub enum Commands {
     
     /// 安装指定版本的 Go
     Install {
-        /// 版本号，例如 1.21.5
-        version: String,
+        /// 版本号（例如 1.21.5）或别名
+        #[arg(required_unless_present = "archive")]
+        version: Option<String>,
+
+        /// 从本地压缩包安装，版本号从包内的 go/VERSION 读取
+        #[arg(long, conflicts_with_all = ["version", "from_dir"])]
+        archive: Option<PathBuf>,
+
+        /// 从本地目录（如 U 盘或 NFS 共享）中查找官方压缩包，而不是下载
+        #[arg(long)]
+        from_dir: Option<PathBuf>,
+
+        /// sha256sum 格式的校验清单，默认使用压缩包所在目录下的 SHA256SUMS
+        #[arg(long)]
+        checksums: Option<PathBuf>,
+
+        /// 安装配置：full 完整安装，slim 去掉测试和文档，minimal 只保留构建所需的内容
+        #[arg(long, value_enum, default_value_t = ProfileArg::Full)]
+        profile: ProfileArg,
+
+        /// 目标操作系统 (GOOS)，默认为当前系统；其他平台的版本单独存放
+        #[arg(long)]
+        os: Option<String>,
+
+        /// 目标架构 (GOARCH)，默认为当前架构，例如 arm64
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// 安装到所有用户共享的根目录（shared.root），需要对该目录有写权限
+        #[arg(long)]
+        shared: bool,
     },
     
     /// 卸载指定版本的 Go
     Uninstall {
-        /// 版本号，例如 1.21.5
+        /// 版本号（例如 1.21.5）、自定义工具链名称或别名
         version: String,
+
+        /// 即使有别名指向该版本也卸载（别名保留）
+        #[arg(long)]
+        force: bool,
+
+        /// 卸载为其他操作系统 (GOOS) 安装的版本
+        #[arg(long)]
+        os: Option<String>,
+
+        /// 卸载为其他架构 (GOARCH) 安装的版本
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// 从共享根目录中卸载，会影响所有用户
+        #[arg(long)]
+        shared: bool,
     },
     
     /// 切换到指定版本的 Go
     Use {
-        /// 版本号，例如 1.21.5
+        /// 版本号（例如 1.21.5）、自定义工具链名称（例如 tip）或别名
         version: String,
+
+        /// 不设置 GOTOOLCHAIN=local，允许 go 命令自动切换工具链
+        #[arg(long)]
+        keep_gotoolchain: bool,
+
+        /// 使用为其他操作系统 (GOOS) 安装的版本
+        #[arg(long)]
+        os: Option<String>,
+
+        /// 使用为其他架构 (GOARCH) 安装的版本
+        #[arg(long)]
+        arch: Option<String>,
+
+        /// 允许切换到不能在本机直接运行的平台（例如已配置 qemu-user）
+        #[arg(long)]
+        allow_foreign: bool,
+    },
+
+    /// 以自定义名称注册本地的 GOROOT（例如自己构建的 gotip 或打过补丁的分支）
+    Link {
+        /// 工具链名称，例如 tip、1.22.3-boringcrypto、myfork
+        name: String,
+
+        /// GOROOT 目录
+        goroot: PathBuf,
+    },
+
+    /// 打印使用指定版本所需的环境变量设置命令
+    Env {
+        /// 版本号、自定义工具链名称或别名，默认为当前版本
+        version: Option<String>,
+
+        /// 不设置 GOTOOLCHAIN=local，允许 go 命令自动切换工具链
+        #[arg(long)]
+        keep_gotoolchain: bool,
     },
     
     /// 显示当前使用的 Go 版本
@@ -40,10 +125,328 @@ pub enum Commands {
     
     /// 清理下载缓存
     Clean,
+
+    /// 显示各版本、下载缓存和临时目录占用的磁盘空间
+    Du {
+        /// 同时统计各版本 go env 中的 GOCACHE 和 GOMODCACHE
+        #[arg(long)]
+        go_caches: bool,
+    },
+
+    /// 将各版本中相同的文件替换为硬链接，之后的安装也会自动去重
+    Dedupe,
+
+    /// 按保留策略清理已安装的版本
+    /// 当前版本和项目 .go-version/go.mod 中要求的版本始终保留
+    Prune {
+        /// 每个次版本号（如 1.21.x）保留最新的补丁版本
+        #[arg(long)]
+        keep_latest_per_minor: bool,
+
+        /// 保留最新的 n 个版本
+        #[arg(long, value_name = "N")]
+        keep: Option<usize>,
+
+        /// 只清理超过该时长未使用（govm use）的版本，例如 90d
+        #[arg(long)]
+        unused_for: Option<String>,
+
+        /// 扫描其中 .go-version 和 go.mod 的项目目录，可重复指定，
+        /// 也可通过 GOVM_PROJECT_ROOTS 设置
+        #[arg(long = "project-root", value_name = "DIR")]
+        project_roots: Vec<PathBuf>,
+
+        /// 只显示将要删除的版本和可回收的空间
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 查看和修改配置（~/.govm/config.toml），环境变量和系统配置 /etc/govm/config.toml 同样生效
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// 管理版本别名（例如 prod -> 1.22.3），别名可以用在 use、install、uninstall 和 env 中
+    Alias {
+        #[command(subcommand)]
+        command: AliasCommands,
+    },
+
+    /// 将 GoVM 的数据目录移动到新位置，并修复 current 链接和激活脚本
+    /// 之后需要设置 GOVM_HOME 指向新目录
+    Relocate {
+        /// 新的数据目录，必须不存在或为空
+        new_dir: PathBuf,
+    },
+
+    /// 查看、校验和按策略清理下载缓存
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
     
     /// 导入系统已安装的 Go
-    Import,
+    Import {
+        /// 要导入的 GOROOT 目录，默认为 PATH 中的 Go
+        #[arg(long, conflicts_with = "all")]
+        path: Option<PathBuf>,
+
+        /// 导入本机发现的所有 Go 安装
+        #[arg(long)]
+        all: bool,
+
+        /// 导入 GOTOOLCHAIN 下载到模块缓存 (GOMODCACHE) 中的工具链
+        #[arg(long, conflicts_with_all = ["path", "all"])]
+        from_modcache: bool,
+
+        /// 导入方式，Unix 默认为 link，Windows 默认为 copy
+        #[arg(long, value_enum)]
+        mode: Option<ImportModeArg>,
+    },
     
+    /// 从源码构建 Go 并安装为受管理的版本，使用已安装的版本自举
+    Build {
+        /// 版本选择器（1.22.3、1.22 或 latest，下载官方源码包）或本地源码目录（例如 git checkout）
+        source: String,
+
+        /// 安装的版本号或自定义工具链名称（例如 tip），默认使用源码中的 VERSION；开发分支的源码必须指定
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// 下载多个平台的官方压缩包到目录（不安装），并写入 SHA256SUMS
+    Download {
+        /// 版本选择器：1.22.3、1.22（最新补丁版本）或 latest
+        selector: String,
+
+        /// 目标平台，逗号分隔，例如 linux/amd64,linux/arm64,windows/amd64；默认为当前平台
+        #[arg(long, value_delimiter = ',', value_parser = Platform::parse)]
+        platform: Vec<Platform>,
+
+        /// 输出目录
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// 下载二进制压缩包还是源码包
+        #[arg(long, value_enum, default_value_t = DownloadKindArg::Archive)]
+        kind: DownloadKindArg,
+    },
+
+    /// 创建或安装包含多个版本的离线包
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommands,
+    },
+
+    /// 将下载缓存作为 go.dev/dl 兼容的镜像提供给局域网或 CI
+    Serve {
+        /// 监听地址
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        listen: String,
+
+        /// 缓存中没有的文件先从上游下载
+        #[arg(long)]
+        fetch_through: bool,
+    },
+
+    /// 从其他 Go 版本管理工具迁移已安装的版本
+    Migrate {
+        /// 原来使用的版本管理工具
+        #[arg(long, value_enum)]
+        from: ManagerArg,
+
+        /// 导入方式，Unix 默认为 link，Windows 默认为 copy
+        #[arg(long, value_enum)]
+        mode: Option<ImportModeArg>,
+    },
+
     /// 显示系统 Go 信息
-    System,
+    System {
+        /// 列出本机发现的所有 Go 安装，而不仅是 PATH 中的第一个
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BundleCommands {
+    /// 将缓存中的压缩包（缺少时先下载）和清单打包成一个文件
+    Create {
+        /// 输出文件，例如 toolchains.govmbundle
+        #[arg(short, long)]
+        output: PathBuf,
+
+        /// 版本号，例如 1.21.5 1.22.3
+        #[arg(required = true)]
+        versions: Vec<String>,
+
+        /// 目标平台，可重复或以逗号分隔，例如 linux/amd64,windows/amd64；默认为当前平台
+        #[arg(long, value_delimiter = ',', value_parser = Platform::parse)]
+        platform: Vec<Platform>,
+    },
+
+    /// 验证并安装离线包中的所有版本
+    Install {
+        /// 离线包文件
+        bundle: PathBuf,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommands {
+    /// 列出缓存中的文件及其版本、平台、大小、时间和校验状态
+    List,
+
+    /// 使用官方发布信息中的 sha256 校验缓存中的压缩包
+    Verify {
+        /// 删除校验失败的压缩包
+        #[arg(long)]
+        delete_invalid: bool,
+    },
+
+    /// 清理旧的或超出大小限制的缓存文件
+    Prune {
+        /// 清理早于该时长的文件，例如 30d、12h
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// 从最旧的文件开始清理，直到缓存不超过该大小，例如 2GB
+        #[arg(long)]
+        max_size: Option<String>,
+
+        /// 保留已安装版本的压缩包
+        #[arg(long)]
+        keep_installed: bool,
+
+        /// 只显示将要删除的文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AliasCommands {
+    /// 设置别名，目标可以是尚未安装的版本
+    Set {
+        /// 别名，例如 prod、legacy-service
+        name: String,
+
+        /// 版本号或自定义工具链名称
+        version: String,
+    },
+
+    /// 列出所有别名
+    List,
+
+    /// 删除别名
+    Rm {
+        /// 别名
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// 显示配置项的生效值及其来源
+    Get {
+        /// 配置项，例如 download.timeout
+        key: String,
+    },
+
+    /// 在用户配置文件中设置配置项
+    Set {
+        /// 配置项，例如 download.mirror
+        key: String,
+
+        /// 配置值，列表以 PATH 分隔符分隔
+        value: String,
+    },
+
+    /// 列出所有配置项的生效值、来源和对应的环境变量
+    List,
+}
+
+/// 安装配置
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileArg {
+    /// 完整的 GOROOT
+    Full,
+    /// 去掉测试文件、testdata 以及 api、doc、misc、test 目录
+    Slim,
+    /// 在 slim 的基础上去掉 src/cmd 源码和 pprof、trace 工具，只保留构建所需的内容
+    Minimal,
+}
+
+impl From<ProfileArg> for Profile {
+    fn from(arg: ProfileArg) -> Self {
+        match arg {
+            ProfileArg::Full => Profile::Full,
+            ProfileArg::Slim => Profile::Slim,
+            ProfileArg::Minimal => Profile::Minimal,
+        }
+    }
+}
+
+/// 导入方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportModeArg {
+    /// 创建指向原安装目录的符号链接
+    Link,
+    /// 复制整个安装目录，原安装被卸载后仍可使用
+    Copy,
+}
+
+impl From<ImportModeArg> for ImportMode {
+    fn from(arg: ImportModeArg) -> Self {
+        match arg {
+            ImportModeArg::Link => ImportMode::Link,
+            ImportModeArg::Copy => ImportMode::Copy,
+        }
+    }
+}
+
+/// govm download 下载的文件类型
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadKindArg {
+    /// 各平台的二进制压缩包
+    Archive,
+    /// 源码包 (goX.src.tar.gz)
+    Source,
+}
+
+impl From<DownloadKindArg> for DownloadKind {
+    fn from(arg: DownloadKindArg) -> Self {
+        match arg {
+            DownloadKindArg::Archive => DownloadKind::Archive,
+            DownloadKindArg::Source => DownloadKind::Source,
+        }
+    }
+}
+
+/// 其他 Go 版本管理工具
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagerArg {
+    /// moovweb/gvm (~/.gvm/gos)
+    Gvm,
+    /// syndbg/goenv (~/.goenv/versions)
+    Goenv,
+    /// asdf-golang (~/.asdf/installs/golang)
+    Asdf,
+    /// voidint/g (~/.g/versions)
+    G,
+    /// golang.org/dl (~/sdk)
+    Sdk,
+}
+
+impl From<ManagerArg> for Manager {
+    fn from(arg: ManagerArg) -> Self {
+        match arg {
+            ManagerArg::Gvm => Manager::Gvm,
+            ManagerArg::Goenv => Manager::Goenv,
+            ManagerArg::Asdf => Manager::Asdf,
+            ManagerArg::G => Manager::G,
+            ManagerArg::Sdk => Manager::Sdk,
+        }
+    }
 }
@@ -0,0 +1,490 @@
+use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
+use reqwest::Client;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use toml::{Table, Value};
+
+use crate::utils::{get_config_dir, portable_root};
+
+/// 配置文件名
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// 配置项的类型
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Str,
+    Int,
+    /// 大于 0 的整数
+    PositiveInt,
+    Bool,
+    /// 只能取列出的值之一
+    Choice(&'static [&'static str]),
+    /// 路径列表，环境变量中以 PATH 分隔符分隔
+    List,
+}
+
+/// 一个配置项：配置文件中的键、覆盖它的环境变量和默认值
+struct Setting {
+    key: &'static str,
+    env: &'static str,
+    kind: Kind,
+    default: &'static str,
+    description: &'static str,
+}
+
+/// 所有配置项
+/// 优先级从高到低：环境变量、用户配置文件、系统配置文件、默认值
+const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "download.mirror",
+        env: "GOVM_DOWNLOAD_URL",
+        kind: Kind::Str,
+        default: "https://go.dev/dl",
+        description: "Download site or mirror (for example another machine's 'govm serve')",
+    },
+    Setting {
+        key: "download.timeout",
+        env: "GOVM_TIMEOUT",
+        kind: Kind::PositiveInt,
+        default: "300",
+        description: "Timeout for a single download, in seconds",
+    },
+    Setting {
+        key: "download.retries",
+        env: "GOVM_RETRIES",
+        kind: Kind::Int,
+        default: "0",
+        description: "How many times a failed download is retried",
+    },
+    Setting {
+        key: "download.proxy",
+        env: "GOVM_PROXY",
+        kind: Kind::Str,
+        default: "",
+        description: "HTTP(S) proxy URL; empty uses HTTPS_PROXY/HTTP_PROXY",
+    },
+    Setting {
+        key: "cache.keep_archives",
+        env: "GOVM_KEEP_ARCHIVES",
+        kind: Kind::Bool,
+        default: "true",
+        description: "Keep downloaded archives in the cache after installing",
+    },
+    Setting {
+        key: "output.color",
+        env: "GOVM_COLOR",
+        kind: Kind::Choice(&["auto", "always", "never"]),
+        default: "auto",
+        description: "Colored output: auto (honors NO_COLOR), always or never",
+    },
+    Setting {
+        key: "output.list_remote_count",
+        env: "GOVM_LIST_REMOTE_COUNT",
+        kind: Kind::Int,
+        default: "30",
+        description: "Number of versions shown by list-remote (0 shows all)",
+    },
+    Setting {
+        key: "shared.root",
+        env: "GOVM_SHARED_ROOT",
+        kind: Kind::Str,
+        default: "",
+        description: "Shared toolchain root used by all users (for example /opt/govm); empty disables it",
+    },
+    Setting {
+        key: "prune.project_roots",
+        env: "GOVM_PROJECT_ROOTS",
+        kind: Kind::List,
+        default: "",
+        description: "Directories scanned by 'govm prune' for .go-version and go.mod",
+    },
+];
+
+/// 配置值的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    System(PathBuf),
+    User(PathBuf),
+    Env(&'static str),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::System(path) | Source::User(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${}", var),
+        }
+    }
+}
+
+/// 生效的配置
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub mirror: String,
+    pub timeout: Duration,
+    pub retries: u32,
+    pub proxy: Option<String>,
+    pub keep_archives: bool,
+    pub color: String,
+    pub list_remote_count: usize,
+    pub project_roots: Vec<PathBuf>,
+    pub shared_root: Option<PathBuf>,
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+static LAYERS: OnceLock<Vec<(Source, Table)>> = OnceLock::new();
+
+/// 系统配置文件，所有用户共享
+pub fn system_config_path() -> PathBuf {
+    if cfg!(windows) {
+        env::var_os("ProgramData")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+            .join("govm")
+            .join(CONFIG_FILE)
+    } else {
+        PathBuf::from("/etc/govm").join(CONFIG_FILE)
+    }
+}
+
+/// 用户配置文件
+pub fn user_config_path() -> Result<PathBuf> {
+    Ok(get_config_dir()?.join(CONFIG_FILE))
+}
+
+fn find_setting(key: &str) -> Result<&'static Setting> {
+    SETTINGS.iter().find(|s| s.key == key).ok_or_else(|| {
+        let keys: Vec<&str> = SETTINGS.iter().map(|s| s.key).collect();
+        anyhow!("Unknown config key: {}. Known keys: {}", key, keys.join(", "))
+    })
+}
+
+/// 将字符串（环境变量或 govm config set 的参数）解析为配置值
+fn parse_value(kind: Kind, value: &str) -> Result<Value> {
+    let value = value.trim();
+    match kind {
+        Kind::Str => Ok(Value::String(value.to_string())),
+        Kind::Int => value
+            .parse::<u32>()
+            .map(|n| Value::Integer(n.into()))
+            .map_err(|_| anyhow!("Expected a non-negative integer, got {:?}", value)),
+        Kind::PositiveInt => value
+            .parse::<u32>()
+            .ok()
+            .filter(|n| *n > 0)
+            .map(|n| Value::Integer(n.into()))
+            .ok_or_else(|| anyhow!("Expected a positive integer, got {:?}", value)),
+        Kind::Bool => match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(Value::Boolean(true)),
+            "false" | "0" | "no" | "off" => Ok(Value::Boolean(false)),
+            _ => Err(anyhow!("Expected true or false, got {:?}", value)),
+        },
+        Kind::Choice(choices) => {
+            if choices.contains(&value) {
+                Ok(Value::String(value.to_string()))
+            } else {
+                Err(anyhow!("Expected one of {}, got {:?}", choices.join(", "), value))
+            }
+        }
+        Kind::List => Ok(Value::Array(
+            env::split_paths(value)
+                .filter(|p| !p.as_os_str().is_empty())
+                .map(|p| Value::String(p.to_string_lossy().to_string()))
+                .collect(),
+        )),
+    }
+}
+
+/// 检查配置文件中的值类型是否正确
+fn check_value(kind: Kind, value: &Value) -> bool {
+    match (kind, value) {
+        (Kind::Str, Value::String(_)) | (Kind::Bool, Value::Boolean(_)) => true,
+        (Kind::Int, Value::Integer(n)) => *n >= 0,
+        (Kind::PositiveInt, Value::Integer(n)) => *n > 0,
+        (Kind::Choice(choices), Value::String(s)) => choices.contains(&s.as_str()),
+        (Kind::List, Value::Array(items)) => items.iter().all(|v| v.is_str()),
+        _ => false,
+    }
+}
+
+/// 按 "section.name" 查找表中的值
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let (section, name) = key.split_once('.')?;
+    table.get(section)?.as_table()?.get(name)
+}
+
+fn read_table(path: &Path) -> Result<Option<Table>> {
+    match fs::read_to_string(path) {
+        Ok(content) => {
+            let table = content
+                .parse::<Table>()
+                .with_context(|| format!("Invalid config file {}", path.display()))?;
+            Ok(Some(table))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// 检查配置文件中所有已知配置项的值
+fn check_layer(source: &Source, table: &Table) -> Result<()> {
+    for setting in SETTINGS {
+        if let Some(value) = lookup(table, setting.key) {
+            if !check_value(setting.kind, value) {
+                return Err(anyhow!("Invalid value for {} in {}: {}", setting.key, source, value));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 读取一个配置文件，文件有误时给出警告并忽略它，以免所有命令（包括 govm config）都无法使用
+fn read_layer(source: Source, path: &Path, layers: &mut Vec<(Source, Table)>) {
+    let checked = match read_table(path) {
+        Ok(Some(table)) => check_layer(&source, &table).map(|_| table),
+        Ok(None) => return,
+        Err(e) => Err(e),
+    };
+    match checked {
+        Ok(table) => layers.push((source, table)),
+        Err(e) => eprintln!("{}", format!("Warning: {:#}\nIgnoring {} until it is fixed", e, path.display()).yellow()),
+    }
+}
+
+/// 已加载的配置文件，优先级从低到高，只在第一次调用时读取
+fn load_layers() -> Result<&'static [(Source, Table)]> {
+    if let Some(layers) = LAYERS.get() {
+        return Ok(layers);
+    }
+
+    let mut layers = Vec::new();
+
+    // 便携模式不受所在机器的系统配置影响
+    if portable_root().is_none() {
+        let system = system_config_path();
+        read_layer(Source::System(system.clone()), &system, &mut layers);
+    }
+
+    let user = user_config_path()?;
+    read_layer(Source::User(user.clone()), &user, &mut layers);
+
+    Ok(LAYERS.get_or_init(|| layers))
+}
+
+/// 计算一个配置项的生效值及其来源
+fn resolve(setting: &Setting, layers: &[(Source, Table)]) -> Result<(Value, Source)> {
+    resolve_with(setting, layers, true)
+}
+
+fn resolve_with(setting: &Setting, layers: &[(Source, Table)], use_env: bool) -> Result<(Value, Source)> {
+    // 空的环境变量视为未设置
+    let from_env = env::var(setting.env).ok().filter(|v| use_env && !v.trim().is_empty());
+    if let Some(value) = from_env {
+        let parsed = parse_value(setting.kind, &value).with_context(|| format!("Invalid ${}", setting.env))?;
+        return Ok((parsed, Source::Env(setting.env)));
+    }
+
+    for (source, table) in layers.iter().rev() {
+        if let Some(value) = lookup(table, setting.key) {
+            if !check_value(setting.kind, value) {
+                return Err(anyhow!("Invalid value for {} in {}: {}", setting.key, source, value));
+            }
+            return Ok((value.clone(), source.clone()));
+        }
+    }
+
+    Ok((parse_value(setting.kind, setting.default)?, Source::Default))
+}
+
+/// 配置值的显示形式，字符串不加引号
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(display_value).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+impl Config {
+    /// 从配置文件和环境变量加载配置
+    pub fn load() -> Result<Self> {
+        Self::from_layers(load_layers()?, true)
+    }
+
+    /// 内置的默认配置
+    fn defaults() -> Self {
+        Self::from_layers(&[], false).expect("built-in defaults are valid")
+    }
+
+    fn from_layers(layers: &[(Source, Table)], use_env: bool) -> Result<Self> {
+        let get = |key: &str| -> Result<Value> { Ok(resolve_with(find_setting(key)?, layers, use_env)?.0) };
+        let int = |key: &str| -> Result<u64> { Ok(get(key)?.as_integer().unwrap_or(0) as u64) };
+
+        let proxy = display_value(&get("download.proxy")?);
+        let shared_root = display_value(&get("shared.root")?);
+
+        Ok(Config {
+            mirror: display_value(&get("download.mirror")?).trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(int("download.timeout")?),
+            retries: int("download.retries")? as u32,
+            proxy: Some(proxy).filter(|p| !p.is_empty()),
+            keep_archives: get("cache.keep_archives")?.as_bool().unwrap_or(true),
+            color: display_value(&get("output.color")?),
+            list_remote_count: int("output.list_remote_count")? as usize,
+            project_roots: get("prune.project_roots")?
+                .as_array()
+                .map(|items| items.iter().filter_map(|v| v.as_str()).map(PathBuf::from).collect())
+                .unwrap_or_default(),
+            shared_root: Some(shared_root).filter(|p| !p.is_empty()).map(PathBuf::from),
+        })
+    }
+}
+
+/// 在启动时加载配置并应用颜色设置
+/// 有误的配置文件会被忽略（给出警告），环境变量有误时报错
+pub fn init() -> Result<&'static Config> {
+    let config = Config::load()?;
+    let config = CONFIG.get_or_init(|| config);
+
+    match config.color.as_str() {
+        "always" => colored::control::set_override(true),
+        "never" => colored::control::set_override(false),
+        _ => {}
+    }
+
+    Ok(config)
+}
+
+/// 获取生效的配置
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().unwrap_or_else(|_| Config::defaults()))
+}
+
+/// 按配置创建 HTTP 客户端（代理）
+pub fn http_client() -> Result<Client> {
+    let mut builder = Client::builder();
+    if let Some(proxy) = &get().proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy).with_context(|| format!("Invalid proxy URL: {}", proxy))?);
+    }
+    Ok(builder.build()?)
+}
+
+/// govm config get：显示生效值及其来源
+pub fn get_value(key: &str) -> Result<()> {
+    let setting = find_setting(key)?;
+    let (value, source) = resolve(setting, load_layers()?)?;
+    println!("{}", display_value(&value));
+    eprintln!("{}", format!("(from {})", source).dimmed());
+    Ok(())
+}
+
+/// govm config set：写入用户配置文件
+pub fn set_value(key: &str, value: &str) -> Result<()> {
+    let setting = find_setting(key)?;
+    let parsed = parse_value(setting.kind, value).with_context(|| format!("Invalid value for {}", key))?;
+
+    let path = user_config_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut table = read_table(&path)?.unwrap_or_default();
+    let (section, name) = key.split_once('.').unwrap_or((key, ""));
+    let section = table
+        .entry(section.to_string())
+        .or_insert_with(|| Value::Table(Table::new()));
+    let section = section
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("{} is not a table in {}", key, path.display()))?;
+    section.insert(name.to_string(), parsed.clone());
+
+    fs::write(&path, toml::to_string_pretty(&table)?)?;
+    println!("✅ {} = {} (in {})", key, display_value(&parsed), path.display());
+
+    if env::var(setting.env).map(|v| !v.trim().is_empty()).unwrap_or(false) {
+        println!("{}", format!("Note: ${} is set and overrides this value", setting.env).yellow());
+    }
+
+    Ok(())
+}
+
+/// govm config list：列出所有配置项的生效值和来源
+pub fn list_values() -> Result<()> {
+    let layers = load_layers()?;
+
+    println!("{}", "GoVM configuration:".bold().green());
+    println!();
+
+    for setting in SETTINGS {
+        let (value, source) = resolve(setting, layers)?;
+        let shown = display_value(&value);
+        let shown = if shown.is_empty() { "(empty)".to_string() } else { shown };
+        let source = match source {
+            Source::Default => "default".dimmed(),
+            Source::Env(_) => source.to_string().yellow(),
+            _ => source.to_string().blue(),
+        };
+        println!("  {:<26} {:<24} {}", setting.key.cyan(), shown, source);
+        println!("  {:<26} {}", "", format!("{} (${})", setting.description, setting.env).dimmed());
+    }
+
+    println!();
+    match portable_root() {
+        Some(root) => println!(
+            "Portable mode ({}): environment variables > {} > defaults",
+            root.display(),
+            user_config_path()?.display()
+        ),
+        None => println!("Precedence: environment variables > {} > {} > defaults",
+            user_config_path()?.display(),
+            system_config_path().display()
+        ),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value(Kind::Int, "60").unwrap(), Value::Integer(60));
+        assert!(parse_value(Kind::Int, "-1").is_err());
+        assert!(parse_value(Kind::PositiveInt, "0").is_err());
+        assert!(!check_value(Kind::PositiveInt, &Value::Integer(0)));
+        assert_eq!(parse_value(Kind::Bool, "no").unwrap(), Value::Boolean(false));
+        assert!(parse_value(Kind::Choice(&["auto", "never"]), "sometimes").is_err());
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let setting = find_setting("output.list_remote_count").unwrap();
+        let system: Table = "[output]\nlist_remote_count = 10\n".parse().unwrap();
+        let user: Table = "[output]\nlist_remote_count = 50\n".parse().unwrap();
+        let layers = vec![
+            (Source::System(PathBuf::from("/etc/govm/config.toml")), system),
+            (Source::User(PathBuf::from("config.toml")), user),
+        ];
+
+        let (value, source) = resolve_with(setting, &layers, false).unwrap();
+        assert_eq!(value, Value::Integer(50));
+        assert_eq!(source, Source::User(PathBuf::from("config.toml")));
+
+        let (value, source) = resolve_with(setting, &layers[..1], false).unwrap();
+        assert_eq!(value, Value::Integer(10));
+        assert!(matches!(source, Source::System(_)));
+
+        let (value, source) = resolve_with(setting, &[], false).unwrap();
+        assert_eq!(value, Value::Integer(30));
+        assert_eq!(source, Source::Default);
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::checksum::sha256_file;
+use crate::utils::{format_size, get_govm_dir, get_versions_dir};
+
+/// 去重结果统计
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DedupeStats {
+    /// 替换为硬链接的文件数
+    pub linked: usize,
+    /// 节省的空间
+    pub saved: u64,
+}
+
+/// 内容寻址存储的目录，存在时 install 会自动去重
+pub fn get_store_dir() -> Result<PathBuf> {
+    Ok(get_govm_dir()?.join("store"))
+}
+
+/// 是否已启用去重（由 govm dedupe 创建存储目录）
+pub fn is_enabled() -> bool {
+    get_store_dir().map(|d| d.is_dir()).unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+#[cfg(unix)]
+fn same_file(a: &fs::Metadata, b: &fs::Metadata) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    a.dev() == b.dev() && a.ino() == b.ino()
+}
+
+/// 文件在存储中的路径：按 sha256 分目录，可执行文件单独存放以保留权限
+#[cfg(unix)]
+fn store_path(store: &Path, hash: &str, metadata: &fs::Metadata) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+    let name = if metadata.permissions().mode() & 0o111 != 0 {
+        format!("{}.x", hash)
+    } else {
+        hash.to_string()
+    };
+    store.join(&hash[..2]).join(name)
+}
+
+/// 将目录中与存储内容相同的文件替换为硬链接
+/// 已有多个链接的文件视为已经去重过，直接跳过
+#[cfg(unix)]
+pub fn dedupe_dir(dir: &Path) -> Result<DedupeStats> {
+    let store = get_store_dir()?;
+    fs::create_dir_all(&store)?;
+    let mut stats = DedupeStats::default();
+
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let metadata = fs::symlink_metadata(path)?;
+        if link_count(&metadata) > 1 {
+            continue;
+        }
+
+        let hash = sha256_file(path)?;
+        let target = store_path(&store, &hash, &metadata);
+
+        match fs::symlink_metadata(&target) {
+            Ok(existing) if same_file(&existing, &metadata) => {}
+            Ok(_) => {
+                // 先在同一目录创建链接再替换，避免中途失败丢失文件
+                let temp = path.with_file_name(format!(
+                    ".{}.govm-link",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                ));
+                let _ = fs::remove_file(&temp);
+                fs::hard_link(&target, &temp)?;
+                fs::rename(&temp, path)?;
+                stats.linked += 1;
+                stats.saved += metadata.len();
+            }
+            Err(_) => {
+                fs::create_dir_all(target.parent().unwrap_or(&store))?;
+                fs::hard_link(path, &target).with_context(|| {
+                    format!(
+                        "Failed to link {} into {} (the store must be on the same filesystem)",
+                        path.display(),
+                        store.display()
+                    )
+                })?;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(not(unix))]
+pub fn dedupe_dir(_dir: &Path) -> Result<DedupeStats> {
+    Err(anyhow!("Deduplication is only supported on Unix"))
+}
+
+/// 删除存储中已经没有版本引用的文件（只剩存储自身一个链接），返回释放的空间
+#[cfg(unix)]
+pub fn collect_garbage() -> Result<u64> {
+    let store = get_store_dir()?;
+    if !store.is_dir() {
+        return Ok(0);
+    }
+
+    let mut freed = 0;
+    for entry in WalkDir::new(&store).min_depth(2) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = fs::symlink_metadata(entry.path())?;
+        if link_count(&metadata) == 1 {
+            fs::remove_file(entry.path())?;
+            freed += metadata.len();
+        }
+    }
+
+    Ok(freed)
+}
+
+#[cfg(not(unix))]
+pub fn collect_garbage() -> Result<u64> {
+    Ok(0)
+}
+
+/// 删除目录后可以释放的空间：只统计没有被其他版本共享的文件
+#[cfg(unix)]
+pub fn reclaimable_size(dir: &Path) -> u64 {
+    // 以链接方式导入的版本删除时只删除链接
+    if fs::symlink_metadata(dir).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        return 0;
+    }
+
+    // 启用去重时存储本身持有一个链接
+    let max_links = if is_enabled() { 2 } else { 1 };
+
+    WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file() && link_count(m) <= max_links)
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(not(unix))]
+pub fn reclaimable_size(dir: &Path) -> u64 {
+    if fs::symlink_metadata(dir).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+        return 0;
+    }
+    crate::utils::dir_size(dir)
+}
+
+/// 计算多个目录的实际占用，硬链接的文件只统计一次
+#[cfg(unix)]
+pub fn unique_size(paths: &[PathBuf]) -> u64 {
+    use std::collections::HashSet;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut seen = HashSet::new();
+    paths
+        .iter()
+        .flat_map(|p| WalkDir::new(p).into_iter().flatten())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file() && seen.insert((m.dev(), m.ino())))
+        .map(|m| m.len())
+        .sum()
+}
+
+#[cfg(not(unix))]
+pub fn unique_size(paths: &[PathBuf]) -> u64 {
+    paths.iter().map(|p| crate::utils::dir_size(p)).sum()
+}
+
+/// 对所有已安装版本去重
+pub fn dedupe_all() -> Result<()> {
+    if cfg!(not(unix)) {
+        return Err(anyhow!("Deduplication is only supported on Unix"));
+    }
+
+    let versions_dir = get_versions_dir()?;
+    let mut dirs: Vec<PathBuf> = fs::read_dir(&versions_dir)?
+        .flatten()
+        .map(|e| e.path())
+        .collect();
+    dirs.sort();
+
+    let mut total = DedupeStats::default();
+
+    for dir in &dirs {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        // 以链接方式导入的版本属于其他安装，不做修改
+        let metadata = fs::symlink_metadata(dir)?;
+        if metadata.file_type().is_symlink() {
+            println!("  {} {} (linked import)", "skip".dimmed(), name);
+            continue;
+        }
+        if !metadata.is_dir() {
+            continue;
+        }
+
+        let stats = dedupe_dir(dir)?;
+        println!("  {} {} ({} files linked, {} saved)", "✓".green(), name, stats.linked, format_size(stats.saved));
+        total.linked += stats.linked;
+        total.saved += stats.saved;
+    }
+
+    println!();
+    println!(
+        "{}",
+        format!("✅ Linked {} duplicate files, saved {}", total.linked, format_size(total.saved)).green()
+    );
+    println!("New installs will be deduplicated automatically.");
+
+    Ok(())
+}
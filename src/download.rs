@@ -1,60 +1,296 @@
 use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
 use futures_util::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::Client;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
+use crate::config::{self, http_client};
+use crate::checksum::{read_sha256sums, sha256_file, verify_sha256, write_sha256sums};
+use crate::utils::{format_size, get_cache_dir};
 use crate::version::GoVersion;
 
-const GO_DOWNLOAD_URL: &str = "https://go.dev/dl";
+/// 获取下载地址
+/// 由配置项 download.mirror（或 GOVM_DOWNLOAD_URL）指定，可以指向镜像，例如另一台机器上的 'govm serve'
+pub fn download_base_url() -> String {
+    config::get().mirror.clone()
+}
 
-/// 获取当前系统的 Go 下载文件名
-pub fn get_go_archive_name(version: &GoVersion) -> Result<String> {
+/// 有官方二进制压缩包的平台 (GOOS, 压缩包中的架构名)
+/// 无法获取发行信息时，用来在下载前判断平台是否有官方构建
+const KNOWN_PLATFORMS: &[(&str, &str)] = &[
+    ("aix", "ppc64"),
+    ("darwin", "amd64"),
+    ("darwin", "arm64"),
+    ("dragonfly", "amd64"),
+    ("freebsd", "386"),
+    ("freebsd", "amd64"),
+    ("freebsd", "arm64"),
+    ("freebsd", "armv6l"),
+    ("freebsd", "riscv64"),
+    ("illumos", "amd64"),
+    ("linux", "386"),
+    ("linux", "amd64"),
+    ("linux", "arm64"),
+    ("linux", "armv6l"),
+    ("linux", "loong64"),
+    ("linux", "mips"),
+    ("linux", "mipsle"),
+    ("linux", "mips64"),
+    ("linux", "mips64le"),
+    ("linux", "ppc64"),
+    ("linux", "ppc64le"),
+    ("linux", "riscv64"),
+    ("linux", "s390x"),
+    ("netbsd", "386"),
+    ("netbsd", "amd64"),
+    ("netbsd", "arm64"),
+    ("netbsd", "armv6l"),
+    ("openbsd", "386"),
+    ("openbsd", "amd64"),
+    ("openbsd", "arm64"),
+    ("openbsd", "armv6l"),
+    ("plan9", "386"),
+    ("plan9", "amd64"),
+    ("plan9", "armv6l"),
+    ("solaris", "amd64"),
+    ("windows", "386"),
+    ("windows", "amd64"),
+    ("windows", "arm64"),
+];
+
+/// 获取当前系统对应的 Go 平台名 (GOOS, GOARCH)
+pub fn host_platform() -> (&'static str, &'static str) {
     let os = std::env::consts::OS;
     let arch = std::env::consts::ARCH;
+    let little_endian = cfg!(target_endian = "little");
 
     // 映射 arch 名称
     let go_arch = match arch {
         "x86_64" => "amd64",
         "aarch64" => "arm64",
         "x86" => "386",
+        "loongarch64" => "loong64",
+        "powerpc64" if little_endian => "ppc64le",
+        "powerpc64" => "ppc64",
+        "mips64" if little_endian => "mips64le",
+        "mips" if little_endian => "mipsle",
+        // arm、riscv64、s390x、mips、mips64 与 GOARCH 同名
         _ => arch,
     };
 
-    let ext = match os {
-        "windows" => "zip",
-        _ => "tar.gz",
-    };
-
     // 映射 os 名称
     let go_os = match os {
         "macos" => "darwin",
         _ => os,
     };
 
-    Ok(format!(
-        "go{}.{}.{}.{}-{}.{}",
-        version.major,
-        version.minor,
-        version.patch,
-        go_os,
-        go_arch,
+    (go_os, go_arch)
+}
+
+/// Go 平台 (GOOS/GOARCH)，架构使用 GOARCH 的写法（32 位 ARM 为 arm）
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Platform {
+    pub os: String,
+    pub arch: String,
+}
+
+impl Platform {
+    pub fn new(os: &str, arch: &str) -> Self {
+        Self {
+            os: os.to_string(),
+            arch: goarch_from_archive(arch).to_string(),
+        }
+    }
+
+    /// 当前系统的平台
+    pub fn host() -> Self {
+        let (os, arch) = host_platform();
+        Self::new(os, arch)
+    }
+
+    /// 由 --os 和 --arch 参数确定平台，未指定的部分使用当前系统的值
+    pub fn from_args(os: Option<&str>, arch: Option<&str>) -> Self {
+        let (host_os, host_arch) = host_platform();
+        Self::new(os.unwrap_or(host_os), arch.unwrap_or(host_arch))
+    }
+
+    pub fn is_host(&self) -> bool {
+        *self == Self::host()
+    }
+
+    /// 解析 "linux/arm64" 或 "linux-arm64"
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let (os, arch) = value
+            .split_once('/')
+            .or_else(|| value.split_once('-'))
+            .filter(|(os, arch)| !os.is_empty() && !arch.is_empty())
+            .ok_or_else(|| anyhow!("Invalid platform: {}. Expected format: linux/arm64", value))?;
+        Ok(Self::new(os, arch))
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.os, self.arch)
+    }
+}
+
+/// GOARCH 对应的压缩包架构名，32 位 ARM 的官方构建为 armv6l
+pub fn archive_arch(goarch: &str) -> &str {
+    match goarch {
+        "arm" => "armv6l",
+        _ => goarch,
+    }
+}
+
+/// 压缩包架构名对应的 GOARCH
+pub fn goarch_from_archive(arch: &str) -> &str {
+    match arch {
+        "armv6l" => "arm",
+        _ => arch,
+    }
+}
+
+/// 按静态规则生成指定平台的压缩包文件名
+pub fn archive_name(version: &GoVersion, os: &str, goarch: &str) -> String {
+    let ext = match os {
+        "windows" => "zip",
+        _ => "tar.gz",
+    };
+
+    format!(
+        "{}.{}-{}.{}",
+        version.to_download_string(),
+        os,
+        archive_arch(goarch),
         ext
+    )
+}
+
+/// 获取当前系统的 Go 下载文件名
+pub fn get_go_archive_name(version: &GoVersion) -> Result<String> {
+    let (go_os, go_arch) = host_platform();
+    Ok(archive_name(version, go_os, go_arch))
+}
+
+/// 在发行信息中查找指定平台的压缩包
+/// 版本不在列表中时返回 None（镜像可能只列出部分版本），版本存在但没有该平台的构建时返回错误
+fn find_archive(
+    releases: &[RemoteRelease],
+    version: &GoVersion,
+    os: &str,
+    goarch: &str,
+) -> Result<Option<RemoteFile>> {
+    let name = version.to_download_string();
+    let release = match releases.iter().find(|r| r.version == name) {
+        Some(r) => r,
+        None => return Ok(None),
+    };
+
+    let arch = archive_arch(goarch);
+    let archives: Vec<&RemoteFile> = release.files.iter().filter(|f| f.kind == "archive").collect();
+
+    if let Some(file) = archives.iter().find(|f| f.os == os && f.arch == arch) {
+        return Ok(Some((*file).clone()));
+    }
+
+    let mut available: Vec<String> = archives.iter().map(|f| format!("{}/{}", f.os, f.arch)).collect();
+    available.sort();
+    available.dedup();
+
+    Err(anyhow!(
+        "No build for {}/{} in {}. Available platforms: {}",
+        os,
+        goarch,
+        name,
+        available.join(", ")
     ))
 }
 
-/// 下载指定版本的 Go
-pub async fn download_go(
+/// 确定要下载的压缩包，优先使用发行信息中的 files[]，无法获取时使用静态映射表
+/// 在下载之前报告平台没有官方构建的情况
+pub async fn resolve_archive(client: &Client, version: &GoVersion, os: &str, goarch: &str) -> Result<RemoteFile> {
+    match fetch_releases(client).await {
+        Ok(releases) => {
+            if let Some(file) = find_archive(&releases, version, os, goarch)? {
+                return Ok(file);
+            }
+        }
+        Err(e) => println!("Could not fetch the release list ({}), using the built-in platform table", e),
+    }
+
+    let arch = archive_arch(goarch);
+    if !KNOWN_PLATFORMS.contains(&(os, arch)) {
+        return Err(anyhow!(
+            "No build for {}/{} in {}: Go does not publish binary archives for this platform",
+            os,
+            goarch,
+            version.to_download_string()
+        ));
+    }
+
+    Ok(RemoteFile {
+        filename: archive_name(version, os, goarch),
+        os: os.to_string(),
+        arch: arch.to_string(),
+        sha256: String::new(),
+        size: 0,
+        kind: "archive".to_string(),
+    })
+}
+
+/// 解析官方压缩包文件名，返回版本、操作系统和架构
+/// 例如 "go1.21.5.linux-amd64.tar.gz" -> (1.21.5, "linux", "amd64")
+pub fn parse_archive_name(file_name: &str) -> Option<(GoVersion, String, String)> {
+    let stem = file_name
+        .strip_suffix(".tar.gz")
+        .or_else(|| file_name.strip_suffix(".zip"))?;
+
+    // 版本和平台之间以最后一个 '.' 分隔: "go1.21.5" + "linux-amd64"
+    let (version_part, platform) = stem.rsplit_once('.')?;
+    let (os, arch) = platform.split_once('-')?;
+    let version = GoVersion::parse(version_part).ok()?;
+
+    Some((version, os.to_string(), arch.to_string()))
+}
+
+/// 下载指定版本和平台的 Go
+pub async fn download_go_for(
     client: &Client,
     version: &GoVersion,
+    platform: &Platform,
+    dest_dir: &Path,
+) -> Result<std::path::PathBuf> {
+    let file = resolve_archive(client, version, &platform.os, &platform.arch).await?;
+    let path = download_archive(client, &file.filename, dest_dir).await?;
+
+    // 发行信息中有 sha256 时校验下载结果
+    if !file.sha256.is_empty() {
+        if let Err(e) = verify_sha256(&path, &file.sha256) {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(e);
+        }
+        println!("✓ Checksum verified");
+    }
+
+    Ok(path)
+}
+
+/// 按文件名下载一个发行文件到目标目录
+/// 先写入临时文件，完成后再重命名，避免留下不完整的文件
+pub async fn download_archive(
+    client: &Client,
+    archive_name: &str,
     dest_dir: &Path,
 ) -> Result<std::path::PathBuf> {
-    let archive_name = get_go_archive_name(version)?;
-    let download_url = format!("{}/{}", GO_DOWNLOAD_URL, archive_name);
-    let dest_path = dest_dir.join(&archive_name);
+    let download_url = format!("{}/{}", download_base_url(), archive_name);
+    let dest_path = dest_dir.join(archive_name);
+    let partial_path = dest_dir.join(format!("{}.download", archive_name));
 
     // 如果文件已存在，先删除
     if dest_path.exists() {
@@ -63,6 +299,33 @@ pub async fn download_go(
 
     println!("Downloading from: {}", download_url);
 
+    // 失败时按配置项 download.retries 重试，每次等待更久
+    let retries = config::get().retries;
+    let mut attempt = 0;
+    loop {
+        match download_once(client, &download_url, &partial_path).await {
+            Ok(()) => break,
+            Err(e) if attempt < retries => {
+                attempt += 1;
+                println!("{}", format!("Download failed ({}). Retrying ({}/{})...", e.root_cause(), attempt, retries).yellow());
+                tokio::time::sleep(Duration::from_secs(2 * attempt as u64)).await;
+            }
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&partial_path).await;
+                return Err(e);
+            }
+        }
+    }
+
+    tokio::fs::rename(&partial_path, &dest_path).await?;
+
+    let size = tokio::fs::metadata(&dest_path).await?.len();
+    println!("Downloaded to: {} ({})", dest_path.display(), format_size(size));
+    Ok(dest_path)
+}
+
+/// 下载一次到临时文件，超时时间由配置项 download.timeout 决定
+async fn download_once(client: &Client, download_url: &str, partial_path: &Path) -> Result<()> {
     // 创建进度条
     let pb = ProgressBar::new(0);
     pb.set_style(
@@ -73,8 +336,8 @@ pub async fn download_go(
 
     // 发送请求
     let response = client
-        .get(&download_url)
-        .timeout(Duration::from_secs(300))
+        .get(download_url)
+        .timeout(config::get().timeout)
         .send()
         .await
         .context("Failed to download Go archive")?;
@@ -94,7 +357,7 @@ pub async fn download_go(
     }
 
     // 创建目标文件
-    let mut file = File::create(&dest_path).await
+    let mut file = File::create(partial_path).await
         .context("Failed to create destination file")?;
 
     // 分块下载
@@ -109,22 +372,44 @@ pub async fn download_go(
     file.flush().await?;
     pb.finish_with_message("Download complete");
 
-    println!("Downloaded to: {}", dest_path.display());
-    Ok(dest_path)
+    Ok(())
 }
 
-/// 获取可用的 Go 版本列表
-pub async fn fetch_remote_versions(client: &Client) -> Result<Vec<GoVersion>> {
+/// 发行 JSON 中的文件条目
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct RemoteFile {
+    pub filename: String,
+    #[serde(default)]
+    pub os: String,
+    /// 压缩包中的架构名，32 位 ARM 为 armv6l
+    #[serde(default)]
+    pub arch: String,
+    #[serde(default)]
+    pub sha256: String,
+    #[serde(default)]
+    pub size: u64,
+    /// archive、installer 或 source
+    #[serde(default)]
+    pub kind: String,
+}
+
+/// 发行 JSON 中的版本条目
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct RemoteRelease {
+    pub version: String,
+    #[serde(default)]
+    pub stable: bool,
+    #[serde(default)]
+    pub files: Vec<RemoteFile>,
+}
+
+/// 获取完整的发行列表（包括每个文件的平台和 sha256）
+pub async fn fetch_releases(client: &Client) -> Result<Vec<RemoteRelease>> {
     // Go 的下载页面 JSON 数据
-    let url = "https://go.dev/dl/?mode=json&include=all";
-    
-    #[derive(Debug, serde::Deserialize)]
-    struct GoRelease {
-        version: String,
-    }
+    let url = format!("{}/?mode=json&include=all", download_base_url());
 
     let response = client
-        .get(url)
+        .get(&url)
         .send()
         .await
         .context("Failed to fetch remote versions")?;
@@ -133,9 +418,16 @@ pub async fn fetch_remote_versions(client: &Client) -> Result<Vec<GoVersion>> {
         return Err(anyhow!("Failed to fetch versions: HTTP {}", response.status()));
     }
 
-    let releases: Vec<GoRelease> = response.json().await
+    let releases: Vec<RemoteRelease> = response.json().await
         .context("Failed to parse versions response")?;
 
+    Ok(releases)
+}
+
+/// 获取可用的 Go 版本列表
+pub async fn fetch_remote_versions(client: &Client) -> Result<Vec<GoVersion>> {
+    let releases = fetch_releases(client).await?;
+
     let mut versions: Vec<GoVersion> = releases
         .into_iter()
         .filter_map(|r| GoVersion::parse(&r.version).ok())
@@ -146,3 +438,239 @@ pub async fn fetch_remote_versions(client: &Client) -> Result<Vec<GoVersion>> {
 
     Ok(versions)
 }
+
+/// govm download 下载的文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadKind {
+    /// 各平台的二进制压缩包
+    Archive,
+    /// 源码包 (goX.src.tar.gz)
+    Source,
+}
+
+/// 按选择器查找发行版本："1.22.3"、"1.22"（该次版本最新的补丁版本）或 "latest"（最新稳定版）
+fn select_release<'a>(releases: &'a [RemoteRelease], selector: &str) -> Result<&'a RemoteRelease> {
+    let selector = selector.trim();
+    let parsed: Vec<(GoVersion, &RemoteRelease)> = releases
+        .iter()
+        .filter_map(|r| GoVersion::parse(&r.version).ok().map(|v| (v, r)))
+        .collect();
+
+    let found = if selector == "latest" {
+        parsed.iter().filter(|(_, r)| r.stable).max_by(|a, b| a.0.cmp(&b.0))
+    } else if let Ok(version) = GoVersion::parse(selector) {
+        parsed.iter().find(|(v, _)| *v == version)
+    } else {
+        let prefix = selector.trim_start_matches("go");
+        let (major, minor) = prefix
+            .split_once('.')
+            .and_then(|(a, b)| Some((a.parse::<u32>().ok()?, b.parse::<u32>().ok()?)))
+            .ok_or_else(|| anyhow!("Invalid version selector: {}. Use 1.22.3, 1.22 or latest", selector))?;
+        parsed
+            .iter()
+            .filter(|(v, _)| v.major == major && v.minor == minor)
+            .max_by(|a, b| a.0.cmp(&b.0))
+    };
+
+    found
+        .map(|(_, r)| *r)
+        .ok_or_else(|| anyhow!("No release matches {}", selector))
+}
+
+/// 将发行文件下载到缓存目录，缓存中已有且校验通过时直接复用
+pub async fn fetch_to_cache(client: &Client, file: &RemoteFile, cache_dir: &Path) -> Result<PathBuf> {
+    let cached = cache_dir.join(&file.filename);
+    let reusable = cached.is_file() && (file.sha256.is_empty() || verify_sha256(&cached, &file.sha256).is_ok());
+
+    if reusable {
+        println!("Using cached {}", file.filename);
+        return Ok(cached);
+    }
+
+    download_archive(client, &file.filename, cache_dir).await?;
+    if !file.sha256.is_empty() {
+        if let Err(e) = verify_sha256(&cached, &file.sha256) {
+            let _ = std::fs::remove_file(&cached);
+            return Err(e);
+        }
+        println!("✓ Checksum verified");
+    }
+
+    Ok(cached)
+}
+
+/// 下载指定版本的源码包到缓存目录，返回版本和文件路径
+/// 无法获取发行信息时只接受完整的版本号，此时不校验 sha256
+pub async fn download_source(client: &Client, selector: &str) -> Result<(GoVersion, PathBuf)> {
+    let file = match fetch_releases(client).await {
+        Ok(releases) => {
+            let release = select_release(&releases, selector)?;
+            release
+                .files
+                .iter()
+                .find(|f| f.kind == "source")
+                .cloned()
+                .ok_or_else(|| anyhow!("No source archive for {}", release.version))?
+        }
+        Err(e) => {
+            let version = GoVersion::parse(selector)
+                .with_context(|| format!("Could not fetch the release list ({})", e))?;
+            RemoteFile {
+                filename: format!("{}.src.tar.gz", version.to_download_string()),
+                os: String::new(),
+                arch: String::new(),
+                sha256: String::new(),
+                size: 0,
+                kind: "source".to_string(),
+            }
+        }
+    };
+
+    let version = GoVersion::parse(file.filename.trim_end_matches(".src.tar.gz"))?;
+    let path = fetch_to_cache(client, &file, &get_cache_dir()?).await?;
+    Ok((version, path))
+}
+
+/// 下载指定版本多个平台的压缩包（或源码包）到目录，并写入 SHA256SUMS
+/// 文件同时保存在缓存中，之后可以直接安装
+pub async fn download_release(
+    selector: &str,
+    platforms: &[Platform],
+    output: &Path,
+    kind: DownloadKind,
+) -> Result<()> {
+    let client = http_client()?;
+    let releases = fetch_releases(&client).await?;
+    let release = select_release(&releases, selector)?;
+    let version = GoVersion::parse(&release.version)?;
+
+    // 先确认所有平台都有构建，再开始下载
+    let files: Vec<RemoteFile> = match kind {
+        DownloadKind::Source => {
+            if !platforms.is_empty() {
+                println!("Note: --platform is ignored for source downloads");
+            }
+            let source = release
+                .files
+                .iter()
+                .find(|f| f.kind == "source")
+                .ok_or_else(|| anyhow!("No source archive for {}", release.version))?;
+            vec![source.clone()]
+        }
+        DownloadKind::Archive => {
+            let host = [Platform::host()];
+            let platforms = if platforms.is_empty() { &host[..] } else { platforms };
+            platforms
+                .iter()
+                .map(|p| {
+                    find_archive(std::slice::from_ref(release), &version, &p.os, &p.arch)?
+                        .ok_or_else(|| anyhow!("No release matches {}", selector))
+                })
+                .collect::<Result<_>>()?
+        }
+    };
+
+    println!("{}", format!("Downloading {} file(s) for {}", files.len(), release.version).blue());
+
+    std::fs::create_dir_all(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let cache_dir = get_cache_dir()?;
+    let sums_path = output.join("SHA256SUMS");
+    let mut sums = if sums_path.is_file() { read_sha256sums(&sums_path)? } else { Default::default() };
+
+    for file in &files {
+        let cached = fetch_to_cache(&client, file, &cache_dir).await?;
+
+        let sha256 = if file.sha256.is_empty() { sha256_file(&cached)? } else { file.sha256.to_lowercase() };
+        let dest = output.join(&file.filename);
+        std::fs::copy(&cached, &dest)
+            .with_context(|| format!("Failed to copy {} to {}", file.filename, output.display()))?;
+        println!("  ✓ {}", dest.display());
+
+        sums.insert(file.filename.clone(), sha256);
+    }
+
+    write_sha256sums(&sums_path, &sums)?;
+
+    println!();
+    println!(
+        "{}",
+        format!("✅ Downloaded {} file(s) to {} (checksums in {})", files.len(), output.display(), sums_path.display())
+            .green()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_archive_name() {
+        let (version, os, arch) = parse_archive_name("go1.21.5.linux-amd64.tar.gz").unwrap();
+        assert_eq!(version, GoVersion::new(1, 21, 5));
+        assert_eq!(os, "linux");
+        assert_eq!(arch, "amd64");
+
+        let (_, os, arch) = parse_archive_name("go1.22.3.windows-arm64.zip").unwrap();
+        assert_eq!((os.as_str(), arch.as_str()), ("windows", "arm64"));
+
+        assert!(parse_archive_name("go1.22.3.src.tar.gz").is_none());
+    }
+
+    #[test]
+    fn test_find_archive() {
+        let file = |os: &str, arch: &str, kind: &str| RemoteFile {
+            filename: format!("go1.21.5.{}-{}.tar.gz", os, arch),
+            os: os.to_string(),
+            arch: arch.to_string(),
+            sha256: "abc".to_string(),
+            size: 0,
+            kind: kind.to_string(),
+        };
+        let releases = vec![RemoteRelease {
+            version: "go1.21.5".to_string(),
+            stable: true,
+            files: vec![file("linux", "amd64", "archive"), file("linux", "armv6l", "archive"), file("", "", "source")],
+        }];
+        let version = GoVersion::new(1, 21, 5);
+
+        let found = find_archive(&releases, &version, "linux", "arm").unwrap().unwrap();
+        assert_eq!(found.filename, "go1.21.5.linux-armv6l.tar.gz");
+
+        let err = find_archive(&releases, &version, "linux", "mips64").unwrap_err().to_string();
+        assert!(err.starts_with("No build for linux/mips64 in go1.21.5"));
+
+        assert!(find_archive(&releases, &GoVersion::new(1, 22, 0), "linux", "amd64").unwrap().is_none());
+        assert_eq!(archive_name(&version, "windows", "arm64"), "go1.21.5.windows-arm64.zip");
+    }
+
+    #[test]
+    fn test_select_release() {
+        let release = |version: &str, stable: bool| RemoteRelease {
+            version: version.to_string(),
+            stable,
+            files: Vec::new(),
+        };
+        let releases = vec![
+            release("go1.23rc1", false),
+            release("go1.22.3", true),
+            release("go1.22.1", true),
+            release("go1.21.5", true),
+        ];
+
+        assert_eq!(select_release(&releases, "latest").unwrap().version, "go1.22.3");
+        assert_eq!(select_release(&releases, "1.21").unwrap().version, "go1.21.5");
+        assert_eq!(select_release(&releases, "1.22.1").unwrap().version, "go1.22.1");
+        assert!(select_release(&releases, "1.20").is_err());
+        assert!(select_release(&releases, "stable-ish").is_err());
+    }
+
+    #[test]
+    fn test_parse_platform() {
+        assert_eq!(Platform::parse("linux/arm64").unwrap(), Platform::new("linux", "arm64"));
+        assert_eq!(Platform::parse("linux-armv6l").unwrap().to_string(), "linux/arm");
+        assert!(Platform::parse("linux").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::dedupe;
+use crate::system_go::{go_exe_name, query_go_env};
+use crate::utils::{dir_size, format_size, get_cache_dir, get_current_dir, get_temp_dir, get_versions_dir};
+
+/// 打印一行用量
+fn print_row(name: &str, size: u64, note: &str) {
+    println!("  {:<24} {:>12}  {}", name, format_size(size), note.dimmed());
+}
+
+/// 显示 GoVM 占用的磁盘空间
+/// `go_caches` 为 true 时还会通过各版本的 go env 查询 GOCACHE 和 GOMODCACHE
+pub fn show_disk_usage(go_caches: bool) -> Result<()> {
+    let versions_dir = get_versions_dir()?;
+    // 硬链接去重后多个目录共享文件，总量按实际占用统计
+    let mut owned: Vec<PathBuf> = Vec::new();
+
+    println!("{}", "Installed versions:".bold().green());
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(&versions_dir)?.flatten().map(|e| e.path()).collect();
+    entries.sort();
+
+    let mut linked_total = 0;
+    for path in &entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let metadata = fs::symlink_metadata(path)?;
+
+        if metadata.file_type().is_symlink() {
+            // 以链接方式导入的版本占用的是原安装的空间，单独统计
+            let target = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            let size = dir_size(&target);
+            linked_total += size;
+            print_row(&name, size, &format!("-> {} (not owned by GoVM)", target.display()));
+        } else if metadata.is_dir() {
+            let size = dir_size(path);
+            owned.push(path.clone());
+            print_row(&name, size, "");
+        }
+    }
+
+    if entries.is_empty() {
+        println!("  {}", "(none)".dimmed());
+    }
+
+    println!();
+    println!("{}", "Other GoVM data:".bold().green());
+
+    // Unix 上 current 是符号链接，Windows 上是复制的目录
+    let current_dir = get_current_dir()?;
+    if fs::symlink_metadata(&current_dir).map(|m| m.is_dir()).unwrap_or(false) {
+        let size = dir_size(&current_dir);
+        owned.push(current_dir);
+        print_row("current", size, "copy of the active version");
+    }
+
+    let cache_dir = get_cache_dir()?;
+    let size = dir_size(&cache_dir);
+    print_row("cache", size, &cache_dir.display().to_string());
+    owned.push(cache_dir);
+
+    let temp_dir = get_temp_dir()?;
+    let size = dir_size(&temp_dir);
+    print_row("temp", size, &temp_dir.display().to_string());
+    owned.push(temp_dir);
+
+    if dedupe::is_enabled() {
+        let store_dir = dedupe::get_store_dir()?;
+        print_row("store", dir_size(&store_dir), "hardlinked into versions, counted once");
+        owned.push(store_dir);
+    }
+
+    let total = dedupe::unique_size(&owned);
+
+    println!();
+    println!("{}", format!("Total used by GoVM: {}", format_size(total)).bold());
+    if linked_total > 0 {
+        println!("Linked imports: {} (freed only by removing the original installations)", format_size(linked_total));
+    }
+
+    if go_caches {
+        show_go_caches(&entries)?;
+    }
+
+    Ok(())
+}
+
+/// 显示各版本使用的 GOCACHE 和 GOMODCACHE，多个版本共用的目录只统计一次
+fn show_go_caches(version_dirs: &[PathBuf]) -> Result<()> {
+    let mut caches: Vec<(String, PathBuf, Vec<String>)> = Vec::new();
+
+    for dir in version_dirs {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let go_bin = dir.join("bin").join(go_exe_name());
+
+        let values = match query_go_env(&go_bin, &["GOCACHE", "GOMODCACHE"]) {
+            Some(values) => values,
+            None => continue,
+        };
+
+        for (var, value) in ["GOCACHE", "GOMODCACHE"].iter().zip(values) {
+            if value.is_empty() || value == "off" {
+                continue;
+            }
+            let path = PathBuf::from(value);
+            match caches.iter_mut().find(|(v, p, _)| v == var && p == &path) {
+                Some((_, _, users)) => users.push(name.clone()),
+                None => caches.push((var.to_string(), path, vec![name.clone()])),
+            }
+        }
+    }
+
+    println!();
+    println!("{}", "Go build and module caches:".bold().green());
+
+    if caches.is_empty() {
+        println!("  {}", "(none found)".dimmed());
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for (var, path, users) in &caches {
+        let size = dir_size(path);
+        total += size;
+        println!("  {:<10} {:>12}  {}", var, format_size(size), path.display());
+        println!("  {:<10} {:>12}  {}", "", "", format!("used by {}", users.join(", ")).dimmed());
+    }
+
+    println!();
+    println!("Total in Go caches: {} (clean with 'go clean -cache' / 'go clean -modcache')", format_size(total));
+
+    Ok(())
+}
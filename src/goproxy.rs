@@ -0,0 +1,248 @@
+use anyhow::Result;
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::FileOptions;
+
+use crate::download::{goarch_from_archive, parse_archive_name};
+use crate::version::GoVersion;
+
+/// Go 1.21+ 通过 GOPROXY 下载工具链时使用的模块路径
+pub const TOOLCHAIN_MODULE: &str = "golang.org/toolchain";
+
+/// 生成的模块 zip 在缓存目录中的子目录
+pub const MODULE_CACHE_DIR: &str = "toolchain-modules";
+
+/// 工具链模块，版本形如 v0.0.1-go1.21.5.linux-amd64
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ToolchainModule {
+    pub version: GoVersion,
+    pub os: String,
+    pub arch: String,
+}
+
+impl ToolchainModule {
+    /// 模块版本号
+    pub fn module_version(&self) -> String {
+        format!("v0.0.1-{}.{}-{}", self.version.to_download_string(), self.os, self.arch)
+    }
+
+    /// 从模块版本号解析
+    pub fn parse(module_version: &str) -> Option<Self> {
+        let rest = module_version.strip_prefix("v0.0.1-")?;
+        let (version, platform) = rest.rsplit_once('.')?;
+        let (os, arch) = platform.split_once('-')?;
+
+        Some(Self {
+            version: GoVersion::parse(version).ok()?,
+            os: os.to_string(),
+            arch: arch.to_string(),
+        })
+    }
+}
+
+/// GOPROXY 请求的处理结果
+pub enum ProxyResponse {
+    /// 文本内容和 Content-Type
+    Text(String, &'static str),
+    /// 需要发送的文件
+    File(PathBuf),
+    NotFound,
+}
+
+/// 列出可以作为工具链模块提供的版本及对应的压缩包
+/// 只使用缓存中未经改动的官方压缩包；已安装的版本可能是源码构建、打过补丁的发行版软件包或链接导入的，
+/// 不能以官方模块版本的名义提供
+pub fn available_toolchains(cache_dir: &Path) -> Result<Vec<(ToolchainModule, PathBuf)>> {
+    let mut modules: Vec<(ToolchainModule, PathBuf)> = Vec::new();
+
+    for entry in fs::read_dir(cache_dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if let Some((version, os, arch)) = parse_archive_name(&name) {
+            // 工具链模块使用 GOARCH（arm），压缩包使用 armv6l
+            let arch = goarch_from_archive(&arch).to_string();
+            modules.push((ToolchainModule { version, os, arch }, entry.path()));
+        }
+    }
+
+    modules.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(modules)
+}
+
+/// 将 Unix 时间戳格式化为 RFC 3339 (UTC)
+fn format_rfc3339(secs: u64) -> String {
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+
+    // 公历日期换算 (Howard Hinnant 的 civil_from_days)
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// 向模块 zip 中写入一个文件
+fn add_zip_file<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    prefix: &str,
+    relative: &str,
+    reader: &mut dyn Read,
+) -> Result<()> {
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(format!("{}/{}", prefix, relative), options)?;
+    std::io::copy(reader, zip)?;
+    Ok(())
+}
+
+/// 由官方压缩包生成工具链模块 zip
+/// 模块 zip 中所有文件位于 "golang.org/toolchain@<版本>/" 下，内容为 GOROOT
+fn write_module_zip(module: &ToolchainModule, archive_path: &Path, dest: &Path) -> Result<()> {
+    let prefix = format!("{}@{}", TOOLCHAIN_MODULE, module.module_version());
+    let file = fs::File::create(dest)?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    if archive_path.to_string_lossy().ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(fs::File::open(archive_path)?)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            if entry.is_dir() {
+                continue;
+            }
+            let relative = match entry.name().strip_prefix("go/") {
+                Some(r) => r.to_string(),
+                None => continue,
+            };
+            add_zip_file(&mut zip, &prefix, &relative, &mut entry)?;
+        }
+    } else {
+        let gz = GzDecoder::new(fs::File::open(archive_path)?);
+        let mut archive = tar::Archive::new(gz);
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let path = entry.path()?.to_string_lossy().to_string();
+            let relative = match path.strip_prefix("go/") {
+                Some(r) => r.to_string(),
+                None => continue,
+            };
+            add_zip_file(&mut zip, &prefix, &relative, &mut entry)?;
+        }
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// 获取（必要时生成）工具链模块 zip，生成结果保存在缓存目录中
+fn module_zip(cache_dir: &Path, module: &ToolchainModule, archive_path: &Path) -> Result<PathBuf> {
+    let dir = cache_dir.join(MODULE_CACHE_DIR);
+    fs::create_dir_all(&dir)?;
+
+    let zip_path = dir.join(format!("{}.zip", module.module_version()));
+    // 早期版本可能由已安装的目录生成过同名 zip，比压缩包旧的一律重新生成
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified()).ok();
+    if zip_path.is_file() && modified(&zip_path) >= modified(archive_path) {
+        return Ok(zip_path);
+    }
+
+    println!("Packing {}@{}...", TOOLCHAIN_MODULE, module.module_version());
+    let partial = dir.join(format!("{}.zip.partial", module.module_version()));
+    if let Err(e) = write_module_zip(module, archive_path, &partial) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::rename(&partial, &zip_path)?;
+
+    Ok(zip_path)
+}
+
+/// 处理 GOPROXY 协议中 golang.org/toolchain/@v/ 之后的请求
+/// 支持 list、<版本>.info、<版本>.mod 和 <版本>.zip
+pub fn handle(cache_dir: &Path, request: &str) -> Result<ProxyResponse> {
+    let toolchains = available_toolchains(cache_dir)?;
+
+    if request == "list" {
+        let list: String = toolchains
+            .iter()
+            .map(|(m, _)| format!("{}\n", m.module_version()))
+            .collect();
+        return Ok(ProxyResponse::Text(list, "text/plain; charset=utf-8"));
+    }
+
+    let (module_version, ext) = match request.rsplit_once('.') {
+        Some(parts) => parts,
+        None => return Ok(ProxyResponse::NotFound),
+    };
+
+    let module = match ToolchainModule::parse(module_version) {
+        Some(m) => m,
+        None => return Ok(ProxyResponse::NotFound),
+    };
+
+    let archive_path = match toolchains.iter().find(|(m, _)| m == &module) {
+        Some((_, path)) => path,
+        None => return Ok(ProxyResponse::NotFound),
+    };
+
+    match ext {
+        "info" => {
+            let secs = fs::metadata(archive_path)?
+                .modified()
+                .unwrap_or_else(|_| SystemTime::now())
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let info = serde_json::json!({
+                "Version": module.module_version(),
+                "Time": format_rfc3339(secs),
+            });
+            Ok(ProxyResponse::Text(info.to_string(), "application/json"))
+        }
+        "mod" => Ok(ProxyResponse::Text(
+            format!("module {}\n", TOOLCHAIN_MODULE),
+            "text/plain; charset=utf-8",
+        )),
+        "zip" => Ok(ProxyResponse::File(module_zip(cache_dir, &module, archive_path)?)),
+        _ => Ok(ProxyResponse::NotFound),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toolchain_module_version() {
+        let module = ToolchainModule::parse("v0.0.1-go1.21.5.linux-amd64").unwrap();
+        assert_eq!(module.version, GoVersion::new(1, 21, 5));
+        assert_eq!((module.os.as_str(), module.arch.as_str()), ("linux", "amd64"));
+        assert_eq!(module.module_version(), "v0.0.1-go1.21.5.linux-amd64");
+
+        assert!(ToolchainModule::parse("v1.0.0").is_none());
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(format_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339(1691452800), "2023-08-08T00:00:00Z");
+    }
+}
@@ -1,22 +1,113 @@
 use anyhow::{Result, anyhow};
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use tar::Archive;
 use flate2::read::GzDecoder;
 use walkdir::WalkDir;
 
-use crate::version::GoVersion;
-use crate::utils::get_versions_dir;
+use crate::config;
+use crate::dedupe;
+use crate::download::Platform;
+use crate::prune;
+use crate::system_go::go_exe_name;
+use crate::version::{is_valid_toolchain_name, GoVersion, Toolchain};
+use crate::utils::{dir_size, format_size, get_govm_dir, get_versions_dir, get_temp_dir};
+
+/// 记录安装配置的标记文件，位于版本目录中
+const PROFILE_MARKER: &str = ".govm-profile";
+
+/// 安装配置，决定解压时保留哪些文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// 完整的 GOROOT
+    #[default]
+    Full,
+    /// 去掉测试文件、testdata 以及 api、doc、misc、test 目录
+    Slim,
+    /// 在 slim 的基础上去掉 src/cmd 源码和 pprof、trace 工具，只保留构建所需的内容
+    Minimal,
+}
+
+impl std::fmt::Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Full => write!(f, "full"),
+            Profile::Slim => write!(f, "slim"),
+            Profile::Minimal => write!(f, "minimal"),
+        }
+    }
+}
+
+impl Profile {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "full" => Some(Profile::Full),
+            "slim" => Some(Profile::Slim),
+            "minimal" => Some(Profile::Minimal),
+            _ => None,
+        }
+    }
+
+    /// 判断压缩包中的条目（路径形如 go/src/fmt/print.go）是否需要解压
+    pub fn includes(&self, entry: &str) -> bool {
+        if *self == Profile::Full {
+            return true;
+        }
+
+        let relative = match entry.strip_prefix("go/") {
+            Some(r) => r.trim_end_matches('/'),
+            None => return true,
+        };
+        let parts: Vec<&str> = relative.split('/').collect();
+
+        if matches!(parts[0], "api" | "doc" | "misc" | "test") {
+            return false;
+        }
+        if parts.contains(&"testdata") || relative.ends_with("_test.go") {
+            return false;
+        }
+
+        if *self == Profile::Minimal {
+            if parts.len() >= 2 && parts[0] == "src" && parts[1] == "cmd" {
+                return false;
+            }
+            // pkg/tool/<平台>/pprof、trace
+            if parts.len() == 4 && parts[0] == "pkg" && parts[1] == "tool" {
+                let tool = parts[3].trim_end_matches(".exe");
+                if tool == "pprof" || tool == "trace" {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// 读取版本目录的安装配置，没有标记文件时为 full
+pub fn read_profile(version_dir: &Path) -> Profile {
+    fs::read_to_string(version_dir.join(PROFILE_MARKER))
+        .ok()
+        .and_then(|c| Profile::parse(&c))
+        .unwrap_or_default()
+}
 
 /// 解压 .tar.gz 文件
-fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+fn extract_tar_gz(archive_path: &Path, dest_dir: &Path, profile: Profile) -> Result<()> {
     let file = fs::File::open(archive_path)?;
     let gz = GzDecoder::new(file);
     let mut archive = Archive::new(gz);
 
     // 解压到临时目录
-    let temp_dir = tempfile::tempdir()?;
-    archive.unpack(temp_dir.path())?;
+    let temp_dir = tempfile::tempdir_in(get_temp_dir()?)?;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_string_lossy().replace('\\', "/");
+        if profile.includes(&path) {
+            entry.unpack_in(temp_dir.path())?;
+        }
+    }
 
     // 移动 go 目录内容到目标目录
     let go_dir = temp_dir.path().join("go");
@@ -29,16 +120,19 @@ fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
 
 /// 解压 .zip 文件 (Windows)
 #[cfg(target_os = "windows")]
-fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+fn extract_zip(archive_path: &Path, dest_dir: &Path, profile: Profile) -> Result<()> {
     use zip::read::ZipArchive;
 
     let file = fs::File::open(archive_path)?;
     let mut archive = ZipArchive::new(file)?;
 
-    let temp_dir = tempfile::tempdir()?;
+    let temp_dir = tempfile::tempdir_in(get_temp_dir()?)?;
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
+        if !profile.includes(file.name()) {
+            continue;
+        }
         let outpath = temp_dir.path().join(file.name());
 
         if file.name().ends_with('/') {
@@ -64,12 +158,12 @@ fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn extract_zip(_archive_path: &Path, _dest_dir: &Path) -> Result<()> {
+fn extract_zip(_archive_path: &Path, _dest_dir: &Path, _profile: Profile) -> Result<()> {
     Err(anyhow!("ZIP extraction is only supported on Windows"))
 }
 
-/// 递归复制目录
-fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
+/// 递归复制目录，符号链接原样复制为链接，不会把链接目标（可能在目录之外）的内容复制进来
+pub fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
     fs::create_dir_all(&dst)?;
     
     for entry in WalkDir::new(&src) {
@@ -78,7 +172,19 @@ fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
         let relative = path.strip_prefix(&src)?;
         let dest_path = dst.as_ref().join(relative);
 
-        if path.is_dir() {
+        if entry.file_type().is_symlink() {
+            let target = fs::read_link(path)?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&target, &dest_path)?;
+            #[cfg(windows)]
+            {
+                if path.is_dir() {
+                    std::os::windows::fs::symlink_dir(&target, &dest_path)?;
+                } else {
+                    std::os::windows::fs::symlink_file(&target, &dest_path)?;
+                }
+            }
+        } else if entry.file_type().is_dir() {
             fs::create_dir_all(&dest_path)?;
         } else {
             fs::copy(path, dest_path)?;
@@ -88,77 +194,435 @@ fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
     Ok(())
 }
 
+/// 压缩包格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    TarGz,
+    Zip,
+}
+
+/// 根据文件名判断压缩包格式
+fn archive_kind(archive_path: &Path) -> Result<ArchiveKind> {
+    let file_name = archive_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+
+    if file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz") {
+        Ok(ArchiveKind::TarGz)
+    } else if file_name.ends_with(".zip") {
+        Ok(ArchiveKind::Zip)
+    } else {
+        Err(anyhow!("Unknown archive format: {}", archive_path.display()))
+    }
+}
+
+/// 完整解压官方压缩包（或源码包）中 go 目录的内容到目标目录
+pub fn extract_archive(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    match archive_kind(archive_path)? {
+        ArchiveKind::TarGz => extract_tar_gz(archive_path, dest_dir, Profile::Full),
+        ArchiveKind::Zip => extract_zip(archive_path, dest_dir, Profile::Full),
+    }
+}
+
+/// 从压缩包内的 go/VERSION 文件读取版本号，不解压整个压缩包
+pub fn read_archive_version(archive_path: &Path) -> Result<GoVersion> {
+    let mut content = String::new();
+
+    match archive_kind(archive_path)? {
+        ArchiveKind::TarGz => {
+            let file = fs::File::open(archive_path)?;
+            let mut archive = Archive::new(GzDecoder::new(file));
+            let mut found = false;
+
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                if entry.path()?.as_ref() == Path::new("go/VERSION") {
+                    entry.read_to_string(&mut content)?;
+                    found = true;
+                    break;
+                }
+            }
+
+            if !found {
+                return Err(anyhow!("{} does not contain go/VERSION", archive_path.display()));
+            }
+        }
+        ArchiveKind::Zip => {
+            let file = fs::File::open(archive_path)?;
+            let mut archive = zip::ZipArchive::new(file)?;
+            let mut entry = archive.by_name("go/VERSION")
+                .map_err(|_| anyhow!("{} does not contain go/VERSION", archive_path.display()))?;
+            entry.read_to_string(&mut content)?;
+        }
+    }
+
+    // 第一行为版本号，例如 "go1.21.5"；1.21 起第二行为构建时间
+    let first_line = content.lines().next().unwrap_or("").trim();
+    GoVersion::parse(first_line).map_err(|_| {
+        anyhow!(
+            "Unrecognized go/VERSION '{}' in {}",
+            first_line,
+            archive_path.display()
+        )
+    })
+}
+
+/// 版本目录名：当前平台为 "1.22.3"，其他平台为 "1.22.3.linux-arm64"
+fn version_dir_name(version: &GoVersion, platform: &Platform) -> String {
+    if platform.is_host() {
+        version.to_dir_name()
+    } else {
+        format!("{}.{}-{}", version.to_dir_name(), platform.os, platform.arch)
+    }
+}
+
+/// 解析版本目录名，返回版本和平台
+fn parse_version_dir_name(name: &str) -> Option<(GoVersion, Platform)> {
+    if let Ok(version) = GoVersion::parse(name) {
+        return Some((version, Platform::host()));
+    }
+
+    let (version, platform) = name.rsplit_once('.')?;
+    Some((GoVersion::parse(version).ok()?, Platform::parse(platform).ok()?))
+}
+
+/// 版本的安装位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    /// 当前用户的 GoVM 目录
+    User,
+    /// 所有用户共享的根目录（shared.root），由管理员或指定的组维护
+    Shared,
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::User => write!(f, "user"),
+            Scope::Shared => write!(f, "shared"),
+        }
+    }
+}
+
+/// 共享根目录中存放版本的目录，未配置 shared.root 时返回 None
+pub fn shared_versions_dir() -> Option<PathBuf> {
+    let root = config::get().shared_root.clone()?;
+    // 共享根目录与自己的 GoVM 目录相同时（例如管理员本人）没有区别
+    if get_govm_dir().map(|d| d == root).unwrap_or(false) {
+        return None;
+    }
+    Some(root.join("versions"))
+}
+
+/// 指定位置中存放版本的目录
+pub fn scope_versions_dir(scope: Scope) -> Result<PathBuf> {
+    match scope {
+        Scope::User => get_versions_dir(),
+        Scope::Shared => shared_versions_dir().ok_or_else(|| {
+            anyhow!("No shared root is configured. Set shared.root in {} or GOVM_SHARED_ROOT", config::system_config_path().display())
+        }),
+    }
+}
+
+/// 查找的顺序：用户目录中的版本优先于共享目录中的同名版本
+fn search_dirs() -> Result<Vec<(Scope, PathBuf)>> {
+    let mut dirs = vec![(Scope::User, get_versions_dir()?)];
+    if let Some(shared) = shared_versions_dir() {
+        dirs.push((Scope::Shared, shared));
+    }
+    Ok(dirs)
+}
+
+/// 用户目录或共享目录中是否存在名为 dir_name 的版本目录（包括链接）
+pub fn has_version_dir(dir_name: &str) -> bool {
+    matches!(find_entry(dir_name), Ok(Some(_)))
+}
+
+/// 按名称查找版本目录，返回所在位置和路径
+fn find_entry(name: &str) -> Result<Option<(Scope, PathBuf)>> {
+    Ok(search_dirs()?
+        .into_iter()
+        .map(|(scope, dir)| (scope, dir.join(name)))
+        .find(|(_, path)| fs::symlink_metadata(path).is_ok()))
+}
+
+/// 获取指定版本和平台的安装目录
+/// 用户目录和共享目录都没有安装时，返回用户目录中的路径
+pub fn version_dir(version: &GoVersion, platform: &Platform) -> Result<PathBuf> {
+    let name = version_dir_name(version, platform);
+    match find_entry(&name)? {
+        Some((_, path)) => Ok(path),
+        None => Ok(get_versions_dir()?.join(name)),
+    }
+}
+
+/// 获取指定位置中的版本目录
+pub fn version_dir_in(scope: Scope, version: &GoVersion, platform: &Platform) -> Result<PathBuf> {
+    Ok(scope_versions_dir(scope)?.join(version_dir_name(version, platform)))
+}
+
+/// 版本安装在哪里，未安装时返回 None
+pub fn version_scope(version: &GoVersion, platform: &Platform) -> Result<Option<Scope>> {
+    Ok(find_entry(&version_dir_name(version, platform))?.map(|(scope, _)| scope))
+}
+
+/// 自定义工具链安装在哪里，未安装时返回 None
+pub fn named_scope(name: &str) -> Result<Option<Scope>> {
+    Ok(find_entry(name)?.map(|(scope, _)| scope))
+}
+
+/// 检查当前用户能否在指定位置安装和卸载版本，返回存放版本的目录
+pub fn check_writable(scope: Scope) -> Result<PathBuf> {
+    let dir = scope_versions_dir(scope)?;
+    let result = fs::create_dir_all(&dir).and_then(|_| tempfile::tempfile_in(&dir).map(|_| ()));
+
+    match result {
+        Ok(()) => Ok(dir),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied && scope == Scope::Shared => Err(anyhow!(
+            "Permission denied: {} is not writable. Shared toolchains are managed by its owner or group; ask an administrator or join that group",
+            dir.display()
+        )),
+        Err(e) => Err(anyhow!("Cannot write to {}: {}", dir.display(), e)),
+    }
+}
+
+/// 让共享安装对所有用户可读（可执行文件和目录可执行），不受安装者 umask 的影响
+/// 共享目录对组可写时，目录同样对组可写，以便组内其他成员卸载
+#[cfg(unix)]
+fn share_with_group(versions_dir: &Path, dir: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let group_writable = fs::metadata(versions_dir)?.permissions().mode() & 0o020 != 0;
+    for entry in WalkDir::new(dir) {
+        let entry = entry?;
+        let file_type = entry.file_type();
+        if file_type.is_symlink() {
+            continue;
+        }
+
+        let mut perms = entry.metadata()?.permissions();
+        let mut mode = perms.mode() | 0o044;
+        if file_type.is_dir() || mode & 0o100 != 0 {
+            mode |= 0o011;
+        }
+        if file_type.is_dir() && group_writable {
+            mode |= 0o020;
+        }
+        if mode != perms.mode() {
+            perms.set_mode(mode);
+            fs::set_permissions(entry.path(), perms)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn share_with_group(_versions_dir: &Path, _dir: &Path) -> Result<()> {
+    Ok(())
+}
+
 /// 安装 Go 版本
-pub fn install_version(archive_path: &Path, version: &GoVersion) -> Result<PathBuf> {
-    let versions_dir = get_versions_dir()?;
-    let version_dir = versions_dir.join(version.to_dir_name());
+/// 其他平台的工具链与当前平台的并列存放，只能用于交叉构建或打包
+/// `profile` 不是 full 时在解压过程中跳过不需要的文件，并在版本目录中写入标记文件
+/// 安装到共享目录前会检查写权限，且不参与去重（存储位于各用户自己的目录中）
+pub fn install_version(
+    archive_path: &Path,
+    version: &GoVersion,
+    platform: &Platform,
+    profile: Profile,
+    scope: Scope,
+) -> Result<PathBuf> {
+    let versions_dir = check_writable(scope)?;
+    let version_dir = versions_dir.join(version_dir_name(version, platform));
 
-    println!("Installing Go {} to: {}", version, version_dir.display());
+    let kind = archive_kind(archive_path)?;
 
-    // 如果目录已存在，先删除
+    println!("Installing Go {} ({}) to: {}", version, platform, version_dir.display());
+
+    // 如果目录已存在，先删除，并清理只被旧目录引用的去重文件
     if version_dir.exists() {
         fs::remove_dir_all(&version_dir)?;
+        dedupe::collect_garbage()?;
     }
 
     fs::create_dir_all(&version_dir)?;
 
-    // 根据文件扩展名选择解压方式
-    let extension = archive_path.extension().and_then(|s| s.to_str());
-    
-    match extension {
-        Some("gz") => extract_tar_gz(archive_path, &version_dir)?,
-        Some("zip") => extract_zip(archive_path, &version_dir)?,
-        _ => {
-            // 尝试根据文件名判断
-            let file_name = archive_path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-            if file_name.ends_with(".tar.gz") {
-                extract_tar_gz(archive_path, &version_dir)?;
-            } else if file_name.ends_with(".zip") {
-                extract_zip(archive_path, &version_dir)?;
-            } else {
-                return Err(anyhow!("Unknown archive format: {}", archive_path.display()));
-            }
-        }
+    // 根据文件名选择解压方式
+    match kind {
+        ArchiveKind::TarGz => extract_tar_gz(archive_path, &version_dir, profile)?,
+        ArchiveKind::Zip => extract_zip(archive_path, &version_dir, profile)?,
+    }
+
+    if profile != Profile::Full {
+        fs::write(version_dir.join(PROFILE_MARKER), format!("{}\n", profile))?;
+        println!("Installed with the {} profile ({})", profile, format_size(dir_size(&version_dir)));
+    }
+
+    if scope == Scope::Shared {
+        share_with_group(&versions_dir, &version_dir)?;
+    } else if dedupe::is_enabled() {
+        let stats = dedupe::dedupe_dir(&version_dir)?;
+        println!("Linked {} files shared with other versions ({} saved)", stats.linked, format_size(stats.saved));
     }
 
     println!("✅ Go {} installed successfully", version);
     Ok(version_dir)
 }
 
-/// 卸载 Go 版本
-pub fn uninstall_version(version: &GoVersion) -> Result<()> {
-    let versions_dir = get_versions_dir()?;
-    let version_dir = versions_dir.join(version.to_dir_name());
+/// 卸载指定位置中的 Go 版本
+pub fn uninstall_version(version: &GoVersion, platform: &Platform, scope: Scope) -> Result<()> {
+    let version_dir = version_dir_in(scope, version, platform)?;
 
     if !version_dir.exists() {
-        return Err(anyhow!("Go {} is not installed", version));
+        return Err(anyhow!("Go {} ({}) is not installed in the {} root", version, platform, scope));
     }
+    check_writable(scope)?;
 
+    let freed = dedupe::reclaimable_size(&version_dir);
     fs::remove_dir_all(&version_dir)?;
-    println!("✅ Go {} uninstalled successfully", version);
+    // 共享文件在最后一个版本删除后才从存储中释放
+    dedupe::collect_garbage()?;
+    // 清理使用记录失败不影响卸载
+    let _ = prune::forget_last_used(&[version_dir_name(version, platform)]);
+    println!("✅ Go {} uninstalled successfully ({} freed)", version, format_size(freed));
 
     Ok(())
 }
 
 /// 检查指定版本是否已安装
-pub fn is_version_installed(version: &GoVersion) -> Result<bool> {
-    let versions_dir = get_versions_dir()?;
-    let version_dir = versions_dir.join(version.to_dir_name());
-    Ok(version_dir.exists())
+pub fn is_version_installed(version: &GoVersion, platform: &Platform) -> Result<bool> {
+    Ok(version_dir(version, platform)?.exists())
 }
 
-/// 获取已安装的版本列表
-pub fn get_installed_versions() -> Result<Vec<GoVersion>> {
-    let versions_dir = get_versions_dir()?;
-    
-    if !versions_dir.exists() {
-        return Ok(Vec::new());
+/// 用户目录和共享目录中的所有条目，同名时只保留用户目录中的
+fn installed_entries() -> Result<Vec<fs::DirEntry>> {
+    let mut entries: Vec<fs::DirEntry> = Vec::new();
+    for (_, dir) in search_dirs()? {
+        let Ok(read_dir) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            if !entries.iter().any(|e| e.file_name() == entry.file_name()) {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// 获取所有平台的已安装版本（包括共享目录中的），按版本从新到旧、当前平台优先排列
+pub fn get_installed_toolchains() -> Result<Vec<(GoVersion, Platform)>> {
+    let mut toolchains = Vec::new();
+
+    for entry in installed_entries()? {
+        if !entry.path().is_dir() {
+            continue;
+        }
+        if let Some(toolchain) = parse_version_dir_name(&entry.file_name().to_string_lossy()) {
+            toolchains.push(toolchain);
+        }
+    }
+
+    toolchains.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then_with(|| b.1.is_host().cmp(&a.1.is_host()))
+            .then_with(|| a.1.cmp(&b.1))
+    });
+
+    Ok(toolchains)
+}
+
+/// 工具链的安装目录，自定义工具链只用于当前平台
+pub fn toolchain_dir(toolchain: &Toolchain) -> Result<PathBuf> {
+    match toolchain {
+        Toolchain::Release(version) => version_dir(version, &Platform::host()),
+        Toolchain::Named(name) => match find_entry(name)? {
+            Some((_, path)) => Ok(path),
+            None => Ok(get_versions_dir()?.join(name)),
+        },
     }
+}
+
+/// 获取自定义名称的工具链（包括共享目录中的），按名称排序
+pub fn get_named_toolchains() -> Result<Vec<String>> {
+    let mut names = Vec::new();
+
+    for entry in installed_entries()? {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_dir() && is_valid_toolchain_name(&name) && parse_version_dir_name(&name).is_none() {
+            names.push(name);
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+/// 将本地的 GOROOT（例如自己构建的 gotip 或打过补丁的分支）以自定义名称注册为工具链
+pub fn link_toolchain(name: &str, goroot: &Path) -> Result<PathBuf> {
+    if !is_valid_toolchain_name(name) || parse_version_dir_name(name).is_some() {
+        return Err(anyhow!(
+            "Invalid toolchain name: {}. Use letters, digits, '.', '_', '-' or '+', and not a plain version number",
+            name
+        ));
+    }
+
+    let go_bin = goroot.join("bin").join(go_exe_name());
+    if !go_bin.is_file() {
+        return Err(anyhow!("{} is not a GOROOT: {} not found", goroot.display(), go_bin.display()));
+    }
+
+    let target = get_versions_dir()?.join(name);
+    if fs::symlink_metadata(&target).is_ok() {
+        return Err(anyhow!("A toolchain named {} already exists at {}", name, target.display()));
+    }
+
+    let source = goroot.canonicalize()?;
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&source, &target)?;
+
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(&source, &target).map_err(|e| {
+        anyhow!("Failed to create directory link ({}). Try running as administrator.", e)
+    })?;
+
+    Ok(target)
+}
+
+/// 卸载指定位置中的自定义工具链，链接注册的只删除链接
+pub fn uninstall_named(name: &str, scope: Scope) -> Result<()> {
+    let dir = scope_versions_dir(scope)?.join(name);
+    let metadata = fs::symlink_metadata(&dir)
+        .map_err(|_| anyhow!("Toolchain {} is not installed in the {} root", name, scope))?;
+    check_writable(scope)?;
+
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(&dir).unwrap_or_default();
+
+        #[cfg(unix)]
+        fs::remove_file(&dir)?;
+
+        #[cfg(windows)]
+        fs::remove_dir(&dir)?;
 
+        let _ = prune::forget_last_used(&[name.to_string()]);
+        println!("✅ Toolchain {} unlinked ({} was left in place)", name, target.display());
+        return Ok(());
+    }
+
+    let freed = dedupe::reclaimable_size(&dir);
+    fs::remove_dir_all(&dir)?;
+    dedupe::collect_garbage()?;
+    let _ = prune::forget_last_used(&[name.to_string()]);
+    println!("✅ Toolchain {} uninstalled successfully ({} freed)", name, format_size(freed));
+
+    Ok(())
+}
+
+/// 获取当前平台已安装的版本列表（包括共享目录中的）
+pub fn get_installed_versions() -> Result<Vec<GoVersion>> {
     let mut versions = Vec::new();
 
-    for entry in fs::read_dir(&versions_dir)? {
-        let entry = entry?;
+    for entry in installed_entries()? {
         let path = entry.path();
         
         if path.is_dir() {
@@ -177,3 +641,55 @@ pub fn get_installed_versions() -> Result<Vec<GoVersion>> {
 
     Ok(versions)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_includes() {
+        assert!(Profile::Full.includes("go/src/fmt/print_test.go"));
+
+        assert!(Profile::Slim.includes("go/src/fmt/print.go"));
+        assert!(Profile::Slim.includes("go/src/cmd/go/main.go"));
+        assert!(!Profile::Slim.includes("go/src/fmt/print_test.go"));
+        assert!(!Profile::Slim.includes("go/src/encoding/json/testdata/code.json.gz"));
+        assert!(!Profile::Slim.includes("go/doc/go_spec.html"));
+        assert!(!Profile::Slim.includes("go/test/"));
+
+        assert!(!Profile::Minimal.includes("go/src/cmd/go/main.go"));
+        assert!(!Profile::Minimal.includes("go/pkg/tool/linux_amd64/pprof"));
+        assert!(Profile::Minimal.includes("go/pkg/tool/linux_amd64/compile"));
+        assert!(Profile::Minimal.includes("go/bin/go"));
+    }
+
+    #[test]
+    fn test_version_dir_name() {
+        let version = GoVersion::new(1, 22, 3);
+        let arm64 = Platform::new("linux", "arm64");
+        let other = if Platform::host() == arm64 { Platform::new("linux", "amd64") } else { arm64 };
+
+        let name = version_dir_name(&version, &other);
+        assert_eq!(name, format!("1.22.3.linux-{}", other.arch));
+        assert_eq!(parse_version_dir_name(&name), Some((version.clone(), other)));
+        assert_eq!(parse_version_dir_name("1.22.3"), Some((version, Platform::host())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_dir_all_keeps_links() {
+        // 解压压缩包时链接不能被解析，否则链接目标（例如 /etc）的内容会被复制进工具链
+        let temp = tempfile::tempdir().unwrap();
+        let outside = temp.path().join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(outside.join("secret"), "x").unwrap();
+
+        let src = temp.path().join("go");
+        fs::create_dir_all(&src).unwrap();
+        std::os::unix::fs::symlink(&outside, src.join("x")).unwrap();
+
+        let dst = temp.path().join("dst");
+        copy_dir_all(&src, &dst).unwrap();
+        assert_eq!(fs::read_link(dst.join("x")).unwrap(), outside);
+    }
+}
@@ -5,23 +5,47 @@ mod install;
 mod switch;
 mod utils;
 mod system_go;
+mod migrate;
+mod checksum;
+mod bundle;
+mod serve;
+mod goproxy;
+mod cache;
+mod prune;
+mod du;
+mod dedupe;
+mod build;
+mod alias;
+mod config;
+mod relocate;
 
 use anyhow::{Result, Context};
 use clap::Parser;
 use colored::Colorize;
-use reqwest::Client;
 use std::io::Write;
-
-use cli::{Cli, Commands};
-use version::GoVersion;
-use install::{install_version, uninstall_version, is_version_installed, get_installed_versions};
-use switch::{switch_version, get_current_version, get_current_go_path};
-use system_go::{detect_system_go, import_system_go, show_path_conflict_warning, get_import_hint};
+use std::path::Path;
+
+use cli::{Cli, Commands, AliasCommands, BundleCommands, CacheCommands, ConfigCommands};
+use config::http_client;
+use download::Platform;
+use version::{GoVersion, Toolchain};
+use install::{
+    install_version, read_archive_version, read_profile, Profile, Scope, uninstall_version, uninstall_named,
+    is_version_installed, get_installed_versions, get_installed_toolchains, get_named_toolchains, link_toolchain,
+};
+use switch::{
+    switch_toolchain, switch_named, get_current_version, get_current_named, get_current_go_path, print_env_commands,
+};
+use system_go::{
+    detect_system_go, discover_system_gos, import_system_go, import_from_path, import_all,
+    show_path_conflict_warning, get_import_hint, ImportMode,
+};
 use utils::get_cache_dir;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    config::init()?;
 
     // 确保必要的目录存在
     let _ = get_cache_dir()?;
@@ -33,14 +57,37 @@ async fn main() -> Result<()> {
         Commands::ListRemote => {
             list_remote_versions().await?;
         }
-        Commands::Install { version } => {
-            install_go_version(&version).await?;
+        Commands::Install { version, archive, from_dir, checksums, profile, os, arch, shared } => {
+            let explicit = (os.is_some() || arch.is_some())
+                .then(|| Platform::from_args(os.as_deref(), arch.as_deref()));
+            let scope = if shared { Scope::Shared } else { Scope::User };
+            let profile = Profile::from(profile);
+            match archive {
+                Some(archive) => {
+                    install_from_archive(&archive, checksums.as_deref(), explicit, profile, scope).await?
+                }
+                None => {
+                    let version = alias::resolve(&version.unwrap_or_default());
+                    let platform = explicit.unwrap_or_else(Platform::host);
+                    install_go_version(&version, from_dir.as_deref(), checksums.as_deref(), &platform, profile, scope)
+                        .await?
+                }
+            }
+        }
+        Commands::Uninstall { version, force, os, arch, shared } => {
+            let platform = Platform::from_args(os.as_deref(), arch.as_deref());
+            uninstall_go_version(&version, &platform, force, shared).await?;
+        }
+        Commands::Use { version, keep_gotoolchain, os, arch, allow_foreign } => {
+            let platform = Platform::from_args(os.as_deref(), arch.as_deref());
+            use_go_version(&version, &platform, allow_foreign, !keep_gotoolchain).await?;
         }
-        Commands::Uninstall { version } => {
-            uninstall_go_version(&version).await?;
+        Commands::Link { name, goroot } => {
+            link_named_toolchain(&name, &goroot)?;
         }
-        Commands::Use { version } => {
-            use_go_version(&version).await?;
+        Commands::Env { version, keep_gotoolchain } => {
+            let version = version.map(|v| alias::resolve(&v));
+            show_env_commands(version.as_deref(), !keep_gotoolchain).await?;
         }
         Commands::Current => {
             show_current_version().await?;
@@ -48,11 +95,101 @@ async fn main() -> Result<()> {
         Commands::Clean => {
             utils::clean_cache()?;
         }
-        Commands::Import => {
-            import_system_go()?;
+        Commands::Du { go_caches } => {
+            du::show_disk_usage(go_caches)?;
+        }
+        Commands::Dedupe => {
+            dedupe::dedupe_all()?;
+        }
+        Commands::Prune { keep_latest_per_minor, keep, unused_for, project_roots, dry_run } => {
+            let policy = prune::PrunePolicy {
+                keep_latest_per_minor,
+                keep,
+                unused_for: unused_for.as_deref().map(utils::parse_duration).transpose()?,
+            };
+            prune::prune_versions(&policy, &project_roots, dry_run)?;
+        }
+        Commands::Config { command } => match command {
+            ConfigCommands::Get { key } => {
+                config::get_value(&key)?;
+            }
+            ConfigCommands::Set { key, value } => {
+                config::set_value(&key, &value)?;
+            }
+            ConfigCommands::List => {
+                config::list_values()?;
+            }
+        },
+        Commands::Alias { command } => match command {
+            AliasCommands::Set { name, version } => {
+                alias::set_alias(&name, &version)?;
+            }
+            AliasCommands::List => {
+                alias::list_aliases()?;
+            }
+            AliasCommands::Rm { name } => {
+                alias::remove_alias(&name)?;
+            }
+        },
+        Commands::Relocate { new_dir } => {
+            relocate::relocate(&new_dir)?;
+        }
+        Commands::Cache { command } => match command {
+            CacheCommands::List => {
+                cache::list_cache()?;
+            }
+            CacheCommands::Verify { delete_invalid } => {
+                cache::verify_cache(delete_invalid).await?;
+            }
+            CacheCommands::Prune { older_than, max_size, keep_installed, dry_run } => {
+                let older_than = older_than.as_deref().map(utils::parse_duration).transpose()?;
+                let max_size = max_size.as_deref().map(utils::parse_size).transpose()?;
+                cache::prune_cache(older_than, max_size, keep_installed, dry_run)?;
+            }
+        },
+        Commands::Import { path, all, from_modcache, mode } => {
+            let mode = mode.map(ImportMode::from).unwrap_or_else(ImportMode::platform_default);
+            if from_modcache {
+                system_go::import_from_modcache(mode)?;
+            } else if all {
+                import_all(mode)?;
+            } else if let Some(path) = path {
+                import_from_path(&path, mode)?;
+            } else {
+                import_system_go(mode)?;
+            }
+        }
+        Commands::Build { source, name } => {
+            build::build_version(&source, name.as_deref()).await?;
+        }
+        Commands::Download { selector, platform, output, kind } => {
+            download::download_release(&selector, &platform, &output, kind.into()).await?;
+        }
+        Commands::Bundle { command } => match command {
+            BundleCommands::Create { output, versions, platform } => {
+                let versions = versions
+                    .iter()
+                    .map(|v| GoVersion::parse(v))
+                    .collect::<Result<Vec<_>>>()
+                    .context("Invalid version format")?;
+                bundle::create_bundle(&output, &versions, &platform).await?;
+            }
+            BundleCommands::Install { bundle } => {
+                bundle::install_bundle(&bundle)?;
+            }
+        },
+        Commands::Serve { listen, fetch_through } => {
+            serve::serve(&listen, fetch_through).await?;
         }
-        Commands::System => {
-            show_system_go_info().await?;
+        Commands::Migrate { from, mode } => {
+            migrate::migrate_from(from.into(), mode.map(ImportMode::from).unwrap_or_else(ImportMode::platform_default))?;
+        }
+        Commands::System { all } => {
+            if all {
+                show_all_system_gos().await?;
+            } else {
+                show_system_go_info().await?;
+            }
         }
     }
 
@@ -69,6 +206,7 @@ async fn show_system_go_info() -> Result<()> {
             println!("  Binary:      {}", system.bin_path.display());
             println!("  GOROOT:      {}", system.path.display());
             println!("  Source:      {}", system.source.to_string().dimmed());
+            println!("  Evidence:    {}", system.evidence.dimmed());
             
             // 检查 PATH 优先级
             match system_go::is_system_go_first_in_path() {
@@ -99,17 +237,94 @@ async fn show_system_go_info() -> Result<()> {
         }
     }
 
+    // 模块缓存中可导入的工具链
+    let versions = get_installed_versions()?;
+    let importable: Vec<String> = system_go::find_modcache_toolchains()
+        .into_iter()
+        .filter(|t| t.is_host_platform() && !versions.contains(&t.module.version))
+        .map(|t| t.module.version.to_string())
+        .collect();
+
+    if !importable.is_empty() {
+        println!();
+        println!("{}", "Toolchains in the Go module cache:".bold());
+        println!("  {}", importable.join(", ").cyan());
+        println!("{}", "  Run 'govm import --from-modcache' to manage them with GoVM".yellow());
+    }
+
     Ok(())
 }
 
+/// 列出本机发现的所有 Go 安装
+async fn show_all_system_gos() -> Result<()> {
+    let installations = discover_system_gos()?;
+
+    if installations.is_empty() {
+        println!("{}", "No system Go installation detected.".yellow());
+        return Ok(());
+    }
+
+    let first_in_path = detect_system_go()?
+        .and_then(|s| s.path.canonicalize().ok());
+    let versions = get_installed_versions()?;
+
+    println!("{}", "System Go installations:".bold().green());
+    println!();
+
+    for system in &installations {
+        let is_active = first_in_path.is_some() && first_in_path == system.path.canonicalize().ok();
+
+        let marker = if is_active { " * ".green().bold() } else { "   ".normal() };
+        let version_str = format!("{:<12}", system.version.to_string());
+
+        let mut statuses = vec![system.source.to_string().dimmed()];
+        if is_active {
+            statuses.push("in PATH".green());
+        }
+        if versions.contains(&system.version) {
+            statuses.push("managed".blue());
+        }
+
+        println!(
+            "{}{} {} ({})",
+            marker,
+            version_str.cyan(),
+            system.path.display(),
+            statuses.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")
+        );
+        println!("                {}", system.evidence.dimmed());
+    }
+
+    println!();
+    println!("{} indicates the Go found first in PATH", "*".green().bold());
+
+    Ok(())
+}
+
+/// 版本位置的显示标签
+fn scope_label(scope: Option<Scope>) -> colored::ColoredString {
+    match scope {
+        Some(Scope::Shared) => "shared".blue(),
+        _ => "local".dimmed(),
+    }
+}
+
 /// 列出已安装的版本
 async fn list_installed_versions() -> Result<()> {
     // 检查 PATH 冲突
     show_path_conflict_warning();
 
-    let versions = get_installed_versions()?;
+    let toolchains = get_installed_toolchains()?;
+    let named = get_named_toolchains()?;
     let current = get_current_version()?;
+    let current_named = get_current_named()?;
     let system_go = detect_system_go()?;
+    let host = Platform::host();
+
+    // Unix 上 current 是符号链接，可以据此区分同一版本的不同平台
+    let current_target = utils::get_current_dir()?.canonicalize().ok();
+    // 配置了共享根目录时标出每个版本的位置
+    let has_shared = install::shared_versions_dir().is_some();
 
     // 显示系统安装的 Go
     if let Some(ref system) = system_go {
@@ -119,13 +334,13 @@ async fn list_installed_versions() -> Result<()> {
         println!("  Source:   {}", system.source.to_string().dimmed());
         println!();
         
-        if !versions.iter().any(|v| v == &system.version) {
+        if !toolchains.iter().any(|(v, p)| v == &system.version && p == &host) {
             println!("{} Run 'govm import' to manage this version.", "Tip:".yellow());
             println!();
         }
     }
 
-    if versions.is_empty() {
+    if toolchains.is_empty() && named.is_empty() {
         println!("{}", "No Go versions installed by GoVM.".yellow());
         println!("Run 'govm install <version>' to install a version,");
         println!("or 'govm import' to import the system Go installation.");
@@ -135,9 +350,13 @@ async fn list_installed_versions() -> Result<()> {
     println!("{}", "Installed Go versions:".bold().green());
     println!();
 
-    for version in &versions {
-        let is_current = current.as_ref() == Some(version);
-        let is_system = system_go.as_ref().map(|s| &s.version) == Some(version);
+    for (version, platform) in &toolchains {
+        let version_dir = install::version_dir(version, platform)?;
+        let is_current = match (&current_target, version_dir.canonicalize()) {
+            (Some(target), Ok(dir)) if cfg!(unix) => *target == dir,
+            _ => current_named.is_none() && current.as_ref() == Some(version) && platform == &host,
+        };
+        let is_system = platform == &host && system_go.as_ref().map(|s| &s.version) == Some(version);
         
         let marker = if is_current {
             " * ".green().bold()
@@ -148,6 +367,8 @@ async fn list_installed_versions() -> Result<()> {
         };
         
         let version_str = format!("{:<12}", version.to_string());
+        let platform_str = format!("{:<15}", platform.to_string());
+        let platform_str = if platform == &host { platform_str.dimmed() } else { platform_str.magenta() };
         
         let mut statuses = Vec::new();
         if is_current {
@@ -156,6 +377,21 @@ async fn list_installed_versions() -> Result<()> {
         if is_system {
             statuses.push("system".blue());
         }
+        let profile = read_profile(&version_dir);
+        if profile != Profile::Full {
+            statuses.push(profile.to_string().yellow());
+        }
+        if build::read_build_info(&version_dir).is_some() {
+            statuses.push("built from source".cyan());
+        }
+        if has_shared {
+            statuses.push(scope_label(install::version_scope(version, platform)?));
+        }
+        if platform == &host {
+            for name in alias::aliases_for(&Toolchain::Release(version.clone())) {
+                statuses.push(format!("alias {}", name).magenta());
+            }
+        }
         
         let status_str = if statuses.is_empty() {
             "".to_string()
@@ -163,7 +399,41 @@ async fn list_installed_versions() -> Result<()> {
             format!("({})", statuses.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", "))
         };
 
-        println!("{}{} {}", marker, version_str.cyan(), status_str);
+        println!("{}{} {} {}", marker, version_str.cyan(), platform_str, status_str);
+    }
+
+    // 自定义工具链排在发行版本之后，显示其 go version 报告的版本
+    for name in &named {
+        let dir = install::toolchain_dir(&Toolchain::Named(name.clone()))?;
+        let is_current = current_named.as_ref() == Some(name);
+        let marker = if is_current { " * ".green().bold() } else { "   ".normal() };
+        let name_str = format!("{:<12}", name);
+        let reported = system_go::go_version_string(&dir).unwrap_or_else(|| "go version unavailable".to_string());
+
+        let mut statuses = Vec::new();
+        if is_current {
+            statuses.push("current".green());
+        }
+        if std::fs::symlink_metadata(&dir).map(|m| m.file_type().is_symlink()).unwrap_or(false) {
+            statuses.push("linked".blue());
+        }
+        if build::read_build_info(&dir).is_some() {
+            statuses.push("built from source".cyan());
+        }
+        if has_shared {
+            statuses.push(scope_label(install::named_scope(name)?));
+        }
+        for alias_name in alias::aliases_for(&Toolchain::Named(name.clone())) {
+            statuses.push(format!("alias {}", alias_name).magenta());
+        }
+
+        let status_str = if statuses.is_empty() {
+            "".to_string()
+        } else {
+            format!("({})", statuses.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", "))
+        };
+
+        println!("{}{} {} {}", marker, name_str.cyan(), reported.dimmed(), status_str);
     }
 
     println!();
@@ -177,15 +447,19 @@ async fn list_installed_versions() -> Result<()> {
 async fn list_remote_versions() -> Result<()> {
     println!("{}", "Fetching remote versions...".blue());
     
-    let client = Client::new();
+    let client = http_client()?;
     let versions = download::fetch_remote_versions(&client).await?;
     let installed = get_installed_versions()?;
 
     println!("\n{}", "Available Go versions:".bold().green());
     println!();
 
-    // 只显示最新的 30 个版本
-    for version in versions.iter().take(30) {
+    // 只显示最新的若干个版本，由配置项 output.list_remote_count 决定，0 表示全部
+    let count = match config::get().list_remote_count {
+        0 => versions.len(),
+        n => n,
+    };
+    for version in versions.iter().take(count) {
         let marker = if installed.contains(version) {
             " ✓ ".green()
         } else {
@@ -209,28 +483,168 @@ async fn list_remote_versions() -> Result<()> {
 }
 
 /// 安装 Go 版本
-async fn install_go_version(version_str: &str) -> Result<()> {
+async fn install_go_version(
+    version_str: &str,
+    from_dir: Option<&Path>,
+    checksums: Option<&Path>,
+    platform: &Platform,
+    profile: Profile,
+    scope: Scope,
+) -> Result<()> {
     let version = GoVersion::parse(version_str)
         .context("Invalid version format")?;
 
     // 检查是否已安装
-    if is_version_installed(&version)? {
-        println!("{}", format!("Go {} is already installed.", version).yellow());
-        println!("Run 'govm use {}' to switch to this version.", version);
+    if is_installed_for(&version, platform, scope)? {
+        print_already_installed(&version, platform);
         return Ok(());
     }
+    // 下载前先检查权限
+    install::check_writable(scope)?;
+
+    if platform.is_host() {
+        println!("{}", format!("Installing Go {}...", version).blue());
+    } else {
+        println!("{}", format!("Installing Go {} for {}...", version, platform).blue());
+    }
 
-    println!("{}", format!("Installing Go {}...", version).blue());
+    let archive_path = match from_dir {
+        Some(dir) => {
+            // 从本地目录查找与当前平台匹配的官方压缩包
+            let archive_path = dir.join(download::archive_name(&version, &platform.os, &platform.arch));
+            if !archive_path.is_file() {
+                return Err(anyhow::anyhow!(
+                    "{} not found in {}",
+                    archive_path.file_name().unwrap_or_default().to_string_lossy(),
+                    dir.display()
+                ));
+            }
 
-    // 创建 HTTP 客户端
-    let client = Client::new();
+            let archive_version = read_archive_version(&archive_path)?;
+            if archive_version != version {
+                return Err(anyhow::anyhow!(
+                    "{} contains Go {}, not Go {}",
+                    archive_path.display(),
+                    archive_version,
+                    version
+                ));
+            }
+
+            archive_path
+        }
+        None => {
+            // 创建 HTTP 客户端
+            let client = http_client()?;
+
+            // 下载
+            let cache_dir = get_cache_dir()?;
+            download::download_go_for(&client, &version, platform, &cache_dir).await?
+        }
+    };
 
-    // 下载
-    let cache_dir = get_cache_dir()?;
-    let archive_path = download::download_go(&client, &version, &cache_dir).await?;
+    verify_archive(&archive_path, checksums)?;
 
     // 安装
-    install_version(&archive_path, &version)?;
+    install_version(&archive_path, &version, platform, profile, scope)?;
+
+    // 配置为不保留下载的压缩包时，安装后从缓存中删除
+    if from_dir.is_none() && !config::get().keep_archives {
+        std::fs::remove_file(&archive_path)?;
+        println!("{}", "Removed the downloaded archive (cache.keep_archives = false)".dimmed());
+    }
+
+    print_install_success(&version, platform);
+
+    Ok(())
+}
+
+/// 从本地压缩包安装 Go
+/// 未通过 --os/--arch 指定平台时，使用官方文件名中的平台，否则视为当前平台
+async fn install_from_archive(
+    archive_path: &Path,
+    checksums: Option<&Path>,
+    platform: Option<Platform>,
+    profile: Profile,
+    scope: Scope,
+) -> Result<()> {
+    if !archive_path.is_file() {
+        return Err(anyhow::anyhow!("Archive not found: {}", archive_path.display()));
+    }
+
+    let version = read_archive_version(archive_path)?;
+    let platform = platform.unwrap_or_else(|| {
+        archive_path
+            .file_name()
+            .and_then(|n| download::parse_archive_name(&n.to_string_lossy()))
+            .map(|(_, os, arch)| Platform::new(&os, &arch))
+            .unwrap_or_else(Platform::host)
+    });
+
+    if is_installed_for(&version, &platform, scope)? {
+        print_already_installed(&version, &platform);
+        return Ok(());
+    }
+
+    println!("{}", format!("Installing Go {} from {}...", version, archive_path.display()).blue());
+
+    verify_archive(archive_path, checksums)?;
+    install_version(archive_path, &version, &platform, profile, scope)?;
+
+    print_install_success(&version, &platform);
+
+    Ok(())
+}
+
+/// 检查版本是否已经可用：安装到用户目录时共享目录中的版本同样可用，
+/// 安装到共享目录时只看共享目录
+fn is_installed_for(version: &GoVersion, platform: &Platform, scope: Scope) -> Result<bool> {
+    match scope {
+        Scope::User => is_version_installed(version, platform),
+        Scope::Shared => Ok(install::version_dir_in(Scope::Shared, version, platform)?.exists()),
+    }
+}
+
+/// 使用校验清单验证压缩包
+/// 未指定清单时使用压缩包所在目录下的 SHA256SUMS（如果存在）
+fn verify_archive(archive_path: &Path, checksums: Option<&Path>) -> Result<()> {
+    let manifest_path = match checksums {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let sibling = archive_path
+                .parent()
+                .map(|p| p.join("SHA256SUMS"))
+                .filter(|p| p.is_file());
+            match sibling {
+                Some(path) => path,
+                None => return Ok(()),
+            }
+        }
+    };
+
+    let manifest = checksum::read_sha256sums(&manifest_path)?;
+    checksum::verify_against_manifest(archive_path, &manifest)?;
+    println!("✓ Checksum verified against {}", manifest_path.display());
+
+    Ok(())
+}
+
+/// 打印版本已安装的提示
+fn print_already_installed(version: &GoVersion, platform: &Platform) {
+    if platform.is_host() {
+        println!("{}", format!("Go {} is already installed.", version).yellow());
+        println!("Run 'govm use {}' to switch to this version.", version);
+    } else {
+        println!("{}", format!("Go {} for {} is already installed.", version, platform).yellow());
+    }
+}
+
+/// 打印安装成功的提示
+fn print_install_success(version: &GoVersion, platform: &Platform) {
+    if !platform.is_host() {
+        println!("\n{}", format!("Go {} for {} installed successfully!", version, platform).green().bold());
+        println!("It cannot run on this {} host; use it for cross builds and images.", Platform::host());
+        return;
+    }
 
     println!("\n{}", format!("Go {} installed successfully!", version).green().bold());
     println!("Run 'govm use {}' to switch to this version.", version);
@@ -240,43 +654,116 @@ async fn install_go_version(version_str: &str) -> Result<()> {
         println!();
         println!("{}", hint.dimmed());
     }
-
-    Ok(())
 }
 
 /// 卸载 Go 版本
-async fn uninstall_go_version(version_str: &str) -> Result<()> {
-    let version = GoVersion::parse(version_str)
-        .context("Invalid version format")?;
+async fn uninstall_go_version(version_str: &str, platform: &Platform, force: bool, shared: bool) -> Result<()> {
+    let toolchain = Toolchain::parse(&alias::resolve(version_str))?;
+
+    // 共享目录中的版本影响所有用户，必须显式指定 --shared
+    let installed = match &toolchain {
+        Toolchain::Release(version) => install::version_scope(version, platform)?,
+        Toolchain::Named(name) => install::named_scope(name)?,
+    };
+    let scope = match (installed, shared) {
+        (_, true) => Scope::Shared,
+        (Some(Scope::Shared), false) => {
+            return Err(anyhow::anyhow!(
+                "Go {} is installed in the shared root {}. Pass --shared to remove it for all users.",
+                toolchain,
+                install::scope_versions_dir(Scope::Shared)?.display()
+            ));
+        }
+        _ => Scope::User,
+    };
+    install::check_writable(scope)?;
+
+    // 别名指向的版本默认不能卸载，避免项目按别名使用时找不到版本
+    let aliases = if platform.is_host() { alias::aliases_for(&toolchain) } else { Vec::new() };
+    if !aliases.is_empty() {
+        if !force {
+            return Err(anyhow::anyhow!(
+                "Go {} is used by alias {}. Point the alias elsewhere, remove it, or pass --force.",
+                toolchain,
+                aliases.join(", ")
+            ));
+        }
+        println!(
+            "{}",
+            format!("Warning: alias {} will point to an uninstalled version.", aliases.join(", ")).yellow()
+        );
+    }
 
     // 检查是否是当前版本
-    if let Some(current) = get_current_version()? {
-        if current == version {
-            println!("{}", format!("Warning: Go {} is the current version.", version).yellow());
-            print!("Are you sure you want to uninstall? [y/N] ");
-            std::io::stdout().flush()?;
-            
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            
-            if !input.trim().eq_ignore_ascii_case("y") {
-                println!("Uninstall cancelled.");
-                return Ok(());
-            }
+    let is_current = match &toolchain {
+        Toolchain::Release(version) => {
+            platform.is_host() && get_current_named()?.is_none() && get_current_version()?.as_ref() == Some(version)
+        }
+        Toolchain::Named(name) => get_current_named()?.as_ref() == Some(name),
+    };
+    if is_current {
+        println!("{}", format!("Warning: Go {} is the current version.", toolchain).yellow());
+        print!("Are you sure you want to uninstall? [y/N] ");
+        std::io::stdout().flush()?;
+        
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        
+        if !input.trim().eq_ignore_ascii_case("y") {
+            println!("Uninstall cancelled.");
+            return Ok(());
         }
     }
 
-    uninstall_version(&version)?;
+    match toolchain {
+        Toolchain::Release(version) => uninstall_version(&version, platform, scope)?,
+        Toolchain::Named(name) => {
+            if !platform.is_host() {
+                return Err(anyhow::anyhow!("Custom toolchains are only installed for the current platform"));
+            }
+            uninstall_named(&name, scope)?
+        }
+    }
 
     Ok(())
 }
 
 /// 切换 Go 版本
-async fn use_go_version(version_str: &str) -> Result<()> {
-    let version = GoVersion::parse(version_str)
-        .context("Invalid version format")?;
+/// 非当前平台的工具链只有在 `allow_foreign` 时才能切换（例如已配置 qemu-user）
+async fn use_go_version(
+    version_str: &str,
+    platform: &Platform,
+    allow_foreign: bool,
+    pin_toolchain: bool,
+) -> Result<()> {
+    let resolved = alias::resolve(version_str);
+    if resolved != version_str {
+        println!("{}", format!("Alias {} -> {}", version_str, resolved).dimmed());
+    }
+
+    let version = match Toolchain::parse(&resolved)? {
+        Toolchain::Release(version) => version,
+        Toolchain::Named(name) => {
+            if !platform.is_host() {
+                return Err(anyhow::anyhow!("Custom toolchains are only installed for the current platform"));
+            }
+            switch_named(&name, pin_toolchain)?;
+            show_path_conflict_warning();
+            return Ok(());
+        }
+    };
+
+    if !platform.is_host() && !allow_foreign {
+        return Err(anyhow::anyhow!(
+            "Go {} for {} cannot run natively on this {} host.\n\
+             Pass --allow-foreign if it can run here (for example under qemu-user binfmt).",
+            version,
+            platform,
+            Platform::host()
+        ));
+    }
 
-    switch_version(&version)?;
+    switch_toolchain(&version, platform, pin_toolchain)?;
     
     // 检查并提示 PATH 冲突
     show_path_conflict_warning();
@@ -284,10 +771,51 @@ async fn use_go_version(version_str: &str) -> Result<()> {
     Ok(())
 }
 
+/// 以自定义名称注册本地 GOROOT
+fn link_named_toolchain(name: &str, goroot: &Path) -> Result<()> {
+    let dir = link_toolchain(name, goroot)?;
+    let reported = system_go::go_version_string(&dir).unwrap_or_else(|| "go version unavailable".to_string());
+
+    println!("{}", format!("✅ Linked toolchain {} -> {}", name, goroot.display()).green());
+    println!("Reports: {}", reported);
+    println!("Run 'govm use {}' to switch to it.", name);
+
+    Ok(())
+}
+
+/// 打印环境变量设置命令
+async fn show_env_commands(version_str: Option<&str>, pin_toolchain: bool) -> Result<()> {
+    let toolchain = match version_str {
+        Some(v) => Toolchain::parse(v)?,
+        None => match (get_current_version()?, get_current_named()?) {
+            (_, Some(name)) => Toolchain::Named(name),
+            (Some(version), None) => Toolchain::Release(version),
+            (None, None) => {
+                return Err(anyhow::anyhow!("No Go version is currently active. Run 'govm use <version>' first."))
+            }
+        },
+    };
+
+    print_env_commands(&toolchain, pin_toolchain)
+}
+
 /// 显示当前版本
 async fn show_current_version() -> Result<()> {
     show_path_conflict_warning();
     
+    // 自定义工具链的 go version 也可能报告发行版本号（例如 boringcrypto 构建），优先按名称显示
+    if let Some(name) = get_current_named()? {
+        let dir = install::toolchain_dir(&Toolchain::Named(name.clone()))?;
+        println!("{}", format!("Current Go toolchain: {}", name).green().bold());
+        if let Some(reported) = system_go::go_version_string(&dir) {
+            println!("Reports:  {}", reported);
+        }
+        if let Some(path) = get_current_go_path()? {
+            println!("Location: {}", path.display());
+        }
+        return Ok(());
+    }
+
     let current = get_current_version()?;
 
     match current {
@@ -0,0 +1,227 @@
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::system_go::{import_go, probe_goroot, ImportMode, Manager};
+use crate::switch::switch_version;
+use crate::utils::get_versions_dir;
+use crate::version::GoVersion;
+
+/// 迁移时需要的工具信息
+impl Manager {
+    /// 读取工具的全局默认版本
+    fn default_version(&self, home: &Path) -> Option<GoVersion> {
+        let root = self.root_dir(home);
+        match self {
+            Manager::Gvm => {
+                let content = fs::read_to_string(root.join("environments").join("default")).ok()?;
+                parse_gvm_default(&content)
+            }
+            Manager::Goenv => {
+                let content = fs::read_to_string(root.join("version")).ok()?;
+                content.lines().find_map(|l| GoVersion::parse(l).ok())
+            }
+            Manager::Asdf => {
+                let content = fs::read_to_string(home.join(".tool-versions")).ok()?;
+                parse_tool_versions(&content)
+            }
+            Manager::G => {
+                // ~/.g/go 是指向当前版本的符号链接
+                let target = fs::read_link(root.join("go")).ok()?;
+                target
+                    .iter()
+                    .rev()
+                    .find_map(|c| GoVersion::parse(&c.to_string_lossy()).ok())
+            }
+            Manager::Sdk => None,
+        }
+    }
+
+    /// 在 shell 配置文件中识别该工具相关行的关键字
+    fn profile_markers(&self) -> &'static [&'static str] {
+        match self {
+            Manager::Gvm => &[".gvm/scripts/gvm", "GVM_ROOT"],
+            Manager::Goenv => &["goenv init", "GOENV_ROOT", ".goenv/bin"],
+            Manager::Asdf => &["plugins/golang", "ASDF_GOLANG"],
+            Manager::G => &[".g/env", "G_HOME", ".g/bin", ".g/go"],
+            Manager::Sdk => &["sdk/go", "golang.org/dl"],
+        }
+    }
+}
+
+/// 从 gvm 的 environments/default 中解析默认版本
+/// 内容形如: export GOROOT; GOROOT="/home/user/.gvm/gos/go1.21.5"
+fn parse_gvm_default(content: &str) -> Option<GoVersion> {
+    content
+        .lines()
+        .filter_map(|l| l.split("GOROOT=").nth(1))
+        .find_map(|value| {
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            let name = Path::new(value).file_name()?.to_string_lossy().to_string();
+            GoVersion::parse(&name).ok()
+        })
+}
+
+/// 从 .tool-versions 中解析 golang 版本
+fn parse_tool_versions(content: &str) -> Option<GoVersion> {
+    content.lines().find_map(|l| {
+        let mut parts = l.split_whitespace();
+        if parts.next() == Some("golang") {
+            parts.next().and_then(|v| GoVersion::parse(v).ok())
+        } else {
+            None
+        }
+    })
+}
+
+/// 确定版本目录中的 GOROOT（asdf 和 g 的版本目录下可能还有一层 go 目录）
+fn resolve_goroot(dir: &Path) -> PathBuf {
+    let nested = dir.join("go");
+    if !dir.join("bin").is_dir() && nested.join("bin").is_dir() {
+        nested
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// shell 配置文件中与旧工具相关的行
+fn find_profile_lines(home: &Path, manager: Manager) -> Vec<(PathBuf, usize, String)> {
+    const PROFILES: &[&str] = &[
+        ".bashrc",
+        ".bash_profile",
+        ".profile",
+        ".zshrc",
+        ".zprofile",
+        ".config/fish/config.fish",
+    ];
+
+    let mut found = Vec::new();
+
+    for name in PROFILES {
+        let path = home.join(name);
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        for (index, line) in content.lines().enumerate() {
+            if manager.profile_markers().iter().any(|m| line.contains(m)) {
+                found.push((path.clone(), index + 1, line.trim().to_string()));
+            }
+        }
+    }
+
+    found
+}
+
+/// 从其他版本管理工具迁移已安装的 Go 版本
+pub fn migrate_from(manager: Manager, mode: ImportMode) -> Result<()> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow!("Failed to get home directory"))?;
+    let store = manager.versions_dir(&home);
+
+    if !store.is_dir() {
+        return Err(anyhow!(
+            "No {} installation found: {} does not exist",
+            manager,
+            store.display()
+        ));
+    }
+
+    println!("{}", format!("Migrating Go versions from {} ({})", manager, store.display()).blue());
+    println!();
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(&store)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    entries.sort();
+
+    let versions_dir = get_versions_dir()?;
+    let mut imported = 0;
+
+    for entry in &entries {
+        let goroot = resolve_goroot(entry);
+        let go = match probe_goroot(&goroot) {
+            Some(go) => go,
+            None => {
+                println!("Skipping {} (not a usable Go installation)", entry.display());
+                continue;
+            }
+        };
+
+        if versions_dir.join(go.version.to_dir_name()).exists() {
+            println!("Skipping Go {} (already managed by GoVM)", go.version);
+            continue;
+        }
+
+        match import_go(&go, mode) {
+            Ok(_) => imported += 1,
+            Err(e) => eprintln!("⚠️  Failed to import {}: {}", entry.display(), e),
+        }
+        println!();
+    }
+
+    println!("Imported {} of {} version(s) from {}", imported, entries.len(), manager);
+
+    // 迁移全局默认版本
+    if let Some(default) = manager.default_version(&home) {
+        println!();
+        if versions_dir.join(default.to_dir_name()).exists() {
+            println!("Setting {}'s default version {} as current", manager, default);
+            switch_version(&default, true)?;
+        } else {
+            println!(
+                "{}",
+                format!("{}'s default version {} was not imported", manager, default).yellow()
+            );
+        }
+    }
+
+    // 提示可以删除的 shell 配置
+    let lines = find_profile_lines(&home, manager);
+    if !lines.is_empty() {
+        println!();
+        println!("{}", format!("The following {} lines in your shell profile can be removed:", manager).bold());
+        for (path, line_no, line) in &lines {
+            println!("  {}:{}: {}", path.display(), line_no, line.dimmed());
+        }
+        if manager == Manager::Asdf {
+            println!("Keep asdf's own setup lines if you still use it for other tools.");
+        }
+    }
+
+    if mode == ImportMode::Link && imported > 0 {
+        println!();
+        println!(
+            "{}",
+            format!(
+                "Imported versions link into {}; use '--mode copy' before uninstalling {}.",
+                store.display(),
+                manager
+            )
+            .dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gvm_default() {
+        let content = "export GVM_ROOT; GVM_ROOT=\"/home/u/.gvm\"\nexport GOROOT; GOROOT=\"$GVM_ROOT/gos/go1.21.5\"\n";
+        assert_eq!(parse_gvm_default(content), Some(GoVersion::new(1, 21, 5)));
+    }
+
+    #[test]
+    fn test_parse_tool_versions() {
+        let content = "nodejs 18.17.0\ngolang 1.22.3 1.21.5\n";
+        assert_eq!(parse_tool_versions(content), Some(GoVersion::new(1, 22, 3)));
+        assert_eq!(parse_tool_versions("python 3.11.4\n"), None);
+    }
+}
@@ -0,0 +1,357 @@
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+use crate::alias::load_aliases;
+use crate::config;
+use crate::dedupe::{collect_garbage, reclaimable_size};
+use crate::install::{get_installed_versions, has_version_dir};
+use crate::switch::get_current_version;
+use crate::utils::{format_age, format_size, get_govm_dir, get_versions_dir};
+use crate::version::GoVersion;
+
+/// 记录各版本最后一次被 use 的时间
+const LAST_USED_FILE: &str = "last-used.json";
+
+/// 扫描项目目录时跳过的目录
+const SKIP_DIRS: &[&str] = &["node_modules", "vendor", "testdata", "target"];
+
+/// 扫描项目目录的最大深度
+const MAX_SCAN_DEPTH: usize = 8;
+
+/// 清理策略，多个策略同时指定时保留任一策略要保留的版本
+#[derive(Debug, Clone, Default)]
+pub struct PrunePolicy {
+    /// 每个次版本号保留最新的补丁版本
+    pub keep_latest_per_minor: bool,
+    /// 保留最新的 n 个版本
+    pub keep: Option<usize>,
+    /// 只清理超过该时长未使用的版本
+    pub unused_for: Option<Duration>,
+}
+
+impl PrunePolicy {
+    fn is_empty(&self) -> bool {
+        !self.keep_latest_per_minor && self.keep.is_none() && self.unused_for.is_none()
+    }
+}
+
+/// 项目中要求的 Go 版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Requirement {
+    /// 完整版本号，例如 .go-version 中的 1.21.5
+    Exact(GoVersion),
+    /// 只有次版本号，例如 .go-version 中的 "1.21"，保护该次版本中最新的已安装版本
+    Minor(u32, u32),
+    /// 最低版本，例如 go.mod 中的 "go 1.21.0" 或 "toolchain go1.22.3"，任何不低于它的版本都能构建项目
+    AtLeast(GoVersion),
+}
+
+fn last_used_path() -> Result<PathBuf> {
+    Ok(get_govm_dir()?.join(LAST_USED_FILE))
+}
+
+fn load_last_used() -> HashMap<String, u64> {
+    last_used_path()
+        .ok()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+/// 记录版本被 use 的时间，供 govm prune --unused-for 使用；`dir_name` 为 versions 下的目录名
+pub fn record_last_used(dir_name: &str) -> Result<()> {
+    let mut records = load_last_used();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    records.insert(dir_name.to_string(), now);
+    fs::write(last_used_path()?, serde_json::to_vec_pretty(&records)?)?;
+    Ok(())
+}
+
+/// 删除已卸载版本的使用记录；用户目录或共享目录中仍有同名目录的保留
+pub fn forget_last_used(dir_names: &[String]) -> Result<()> {
+    let mut records = load_last_used();
+    let before = records.len();
+    for name in dir_names {
+        if !has_version_dir(name) {
+            records.remove(name);
+        }
+    }
+
+    if records.len() != before {
+        fs::write(last_used_path()?, serde_json::to_vec_pretty(&records)?)?;
+    }
+    Ok(())
+}
+
+/// 版本最后一次使用的时间，从未 use 过的版本使用安装时间
+fn last_used(version: &GoVersion, records: &HashMap<String, u64>, versions_dir: &Path) -> SystemTime {
+    if let Some(secs) = records.get(&version.to_dir_name()) {
+        return UNIX_EPOCH + Duration::from_secs(*secs);
+    }
+
+    fs::symlink_metadata(versions_dir.join(version.to_dir_name()))
+        .and_then(|m| m.modified())
+        .unwrap_or(UNIX_EPOCH)
+}
+
+/// 解析项目中的版本号，"1.21"、"1.21rc1" 等只保留次版本号
+fn parse_requirement(value: &str) -> Option<Requirement> {
+    let value = value.trim().trim_start_matches("go");
+    if let Ok(version) = GoVersion::parse(value) {
+        return Some(Requirement::Exact(version));
+    }
+
+    let mut parts = value.splitn(3, '.');
+    let major = parts.next()?.parse().ok()?;
+    let minor: String = parts.next()?.chars().take_while(|c| c.is_ascii_digit()).collect();
+    Some(Requirement::Minor(major, minor.parse().ok()?))
+}
+
+/// 从 go.mod 中读取 go 和 toolchain 指令，两者都是最低版本要求
+/// "go 1.21" 和 "go 1.21rc1" 按 1.21.0 处理
+fn parse_go_mod(content: &str) -> Vec<Requirement> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("go") | Some("toolchain") => parts.next().and_then(parse_requirement),
+                _ => None,
+            }
+        })
+        .map(|requirement| match requirement {
+            Requirement::Exact(version) => Requirement::AtLeast(version),
+            Requirement::Minor(major, minor) => Requirement::AtLeast(GoVersion::new(major, minor, 0)),
+            other => other,
+        })
+        .collect()
+}
+
+/// 满足要求的已安装版本；versions 需按从新到旧排序
+/// 最低版本要求优先选同一次版本中最新的，没有时选不低于它的最新版本
+fn satisfying(versions: &[GoVersion], requirement: &Requirement) -> Option<GoVersion> {
+    match requirement {
+        Requirement::Exact(v) => versions.contains(v).then(|| v.clone()),
+        Requirement::Minor(major, minor) => versions.iter().find(|v| v.major == *major && v.minor == *minor).cloned(),
+        Requirement::AtLeast(min) => versions
+            .iter()
+            .find(|v| v.major == min.major && v.minor == min.minor && *v >= min)
+            .or_else(|| versions.iter().find(|v| *v >= min))
+            .cloned(),
+    }
+}
+
+/// 项目根目录，来自 --project-root 和配置项 prune.project_roots（或 GOVM_PROJECT_ROOTS）
+fn project_roots(extra: &[PathBuf]) -> Vec<PathBuf> {
+    let mut roots = extra.to_vec();
+    roots.extend(config::get().project_roots.iter().cloned());
+    roots
+}
+
+/// 扫描项目根目录中的 .go-version 和 go.mod
+fn scan_projects(roots: &[PathBuf]) -> Vec<(Requirement, PathBuf)> {
+    let mut found = Vec::new();
+
+    for root in roots {
+        let walker = WalkDir::new(root).max_depth(MAX_SCAN_DEPTH).into_iter().filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0 || !e.file_type().is_dir() || !(name.starts_with('.') || SKIP_DIRS.contains(&name.as_ref()))
+        });
+
+        for entry in walker.flatten() {
+            let requirements = match entry.file_name().to_str() {
+                Some(".go-version") => fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|c| c.lines().next().and_then(parse_requirement))
+                    .into_iter()
+                    .collect(),
+                Some("go.mod") => fs::read_to_string(entry.path())
+                    .map(|c| parse_go_mod(&c))
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+
+            for requirement in requirements {
+                found.push((requirement, entry.path().to_path_buf()));
+            }
+        }
+    }
+
+    found
+}
+
+/// 按策略决定保留哪些版本，返回保留原因；versions 需按从新到旧排序
+fn apply_policy(
+    versions: &[GoVersion],
+    policy: &PrunePolicy,
+    last_used: impl Fn(&GoVersion) -> SystemTime,
+) -> BTreeMap<GoVersion, String> {
+    let mut kept = BTreeMap::new();
+
+    if let Some(n) = policy.keep {
+        for version in versions.iter().take(n) {
+            kept.entry(version.clone()).or_insert_with(|| format!("within --keep {}", n));
+        }
+    }
+
+    if policy.keep_latest_per_minor {
+        let mut seen = Vec::new();
+        for version in versions {
+            if !seen.contains(&(version.major, version.minor)) {
+                seen.push((version.major, version.minor));
+                kept.entry(version.clone()).or_insert_with(|| "latest patch of its minor".to_string());
+            }
+        }
+    }
+
+    if let Some(limit) = policy.unused_for {
+        for version in versions {
+            let age = SystemTime::now().duration_since(last_used(version)).unwrap_or_default();
+            if age <= limit {
+                kept.entry(version.clone())
+                    .or_insert_with(|| format!("used {} ago", format_age(age)));
+            }
+        }
+    }
+
+    kept
+}
+
+/// 按保留策略清理已安装的版本
+pub fn prune_versions(policy: &PrunePolicy, roots: &[PathBuf], dry_run: bool) -> Result<()> {
+    if policy.is_empty() {
+        return Err(anyhow!("Specify at least one of --keep-latest-per-minor, --keep or --unused-for"));
+    }
+
+    // 只清理自己目录中的版本，共享目录中的版本由管理员维护
+    let versions_dir = get_versions_dir()?;
+    let versions: Vec<GoVersion> = get_installed_versions()?
+        .into_iter()
+        .filter(|v| fs::symlink_metadata(versions_dir.join(v.to_dir_name())).is_ok())
+        .collect();
+    let records = load_last_used();
+
+    let mut protected: BTreeMap<GoVersion, String> = BTreeMap::new();
+    if let Some(current) = get_current_version()? {
+        protected.insert(current, "current".to_string());
+    }
+
+    // 别名指向的版本同样受保护
+    for (name, target) in load_aliases() {
+        if let Ok(version) = GoVersion::parse(&target) {
+            protected.entry(version).or_insert_with(|| format!("alias {}", name));
+        }
+    }
+
+    let roots = project_roots(roots);
+    for (requirement, file) in scan_projects(&roots) {
+        if let Some(version) = satisfying(&versions, &requirement) {
+            protected.entry(version).or_insert_with(|| format!("required by {}", file.display()));
+        }
+    }
+
+    let kept = apply_policy(&versions, policy, |v| last_used(v, &records, &versions_dir));
+
+    let mut reclaimed = 0;
+    let mut removed = Vec::new();
+
+    for version in &versions {
+        if let Some(reason) = protected.get(version).or_else(|| kept.get(version)) {
+            println!("  {} Go {} ({})", "keep".green(), version, reason.dimmed());
+            continue;
+        }
+
+        let dir = versions_dir.join(version.to_dir_name());
+        // 以链接方式导入的版本只删除链接，不释放空间
+        let is_link = fs::symlink_metadata(&dir).map(|m| m.file_type().is_symlink()).unwrap_or(false);
+        let size = if is_link { 0 } else { reclaimable_size(&dir) };
+        let detail = if is_link { "link".to_string() } else { format_size(size) };
+
+        if dry_run {
+            println!("  {} Go {} ({})", "would remove".yellow(), version, detail);
+        } else {
+            if is_link {
+                fs::remove_file(&dir)?;
+            } else {
+                fs::remove_dir_all(&dir)?;
+            }
+            println!("  {} Go {} ({})", "removed".red(), version, detail);
+        }
+
+        reclaimed += size;
+        removed.push(version.to_dir_name());
+    }
+
+    if !dry_run && !removed.is_empty() {
+        collect_garbage()?;
+        forget_last_used(&removed)?;
+    }
+
+    println!();
+    if removed.is_empty() {
+        println!("Nothing to prune.");
+    } else if dry_run {
+        println!("Would remove {} version(s) and reclaim {}", removed.len(), format_size(reclaimed));
+    } else {
+        println!(
+            "{}",
+            format!("✅ Removed {} version(s), reclaimed {}", removed.len(), format_size(reclaimed)).green()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_go_mod() {
+        let content = "module example.com/m\n\ngo 1.21\n\ntoolchain go1.22.3\n\nrequire golang.org/x/mod v0.14.0\n";
+        assert_eq!(
+            parse_go_mod(content),
+            [Requirement::AtLeast(GoVersion::new(1, 21, 0)), Requirement::AtLeast(GoVersion::new(1, 22, 3))]
+        );
+        assert_eq!(parse_requirement("1.23rc1"), Some(Requirement::Minor(1, 23)));
+    }
+
+    #[test]
+    fn test_satisfying() {
+        let versions = [GoVersion::new(1, 22, 3), GoVersion::new(1, 21, 5)];
+
+        // go 1.21.0 只安装了 1.21.5 时仍然受保护
+        let go_mod = parse_go_mod("module m\n\ngo 1.21.0\n");
+        assert_eq!(satisfying(&versions, &go_mod[0]), Some(GoVersion::new(1, 21, 5)));
+        // 同一次版本中没有满足的版本时选更新的版本
+        let toolchain = Requirement::AtLeast(GoVersion::new(1, 21, 8));
+        assert_eq!(satisfying(&versions, &toolchain), Some(GoVersion::new(1, 22, 3)));
+        assert_eq!(satisfying(&versions, &Requirement::AtLeast(GoVersion::new(1, 23, 0))), None);
+        assert_eq!(satisfying(&versions, &Requirement::Exact(GoVersion::new(1, 21, 0))), None);
+    }
+
+    #[test]
+    fn test_apply_policy() {
+        let versions = [
+            GoVersion::new(1, 22, 3),
+            GoVersion::new(1, 22, 1),
+            GoVersion::new(1, 21, 5),
+            GoVersion::new(1, 21, 4),
+        ];
+
+        let policy = PrunePolicy { keep_latest_per_minor: true, ..Default::default() };
+        let kept = apply_policy(&versions, &policy, |_| UNIX_EPOCH);
+        assert_eq!(kept.keys().cloned().collect::<Vec<_>>(), [GoVersion::new(1, 21, 5), GoVersion::new(1, 22, 3)]);
+
+        let policy = PrunePolicy { keep: Some(1), unused_for: Some(Duration::from_secs(86400)), ..Default::default() };
+        let kept = apply_policy(&versions, &policy, |v| {
+            if v.patch == 4 { SystemTime::now() } else { UNIX_EPOCH }
+        });
+        assert_eq!(kept.keys().cloned().collect::<Vec<_>>(), [GoVersion::new(1, 21, 4), GoVersion::new(1, 22, 3)]);
+    }
+}
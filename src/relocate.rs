@@ -0,0 +1,304 @@
+use anyhow::{Result, anyhow};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::CONFIG_FILE;
+use crate::utils::{format_size, dir_size, layout, portable_root, Layout};
+
+/// 激活脚本中写入了绝对路径，移动后需要改写
+const ACTIVATION_SCRIPTS: &[&str] = &["activate.sh", "activate.bat", "activate.ps1"];
+
+/// 将 GoVM 的所有数据移动到新目录
+/// 同一文件系统内直接重命名，否则复制（保留符号链接和去重产生的硬链接）后删除原目录。
+/// 使用 XDG 布局时，缓存和配置文件也会移动到新目录中
+pub fn relocate(new_dir: &Path) -> Result<()> {
+    if let Some(root) = portable_root() {
+        return Err(anyhow!(
+            "GoVM is running in portable mode from {}; move that whole directory instead",
+            root.display()
+        ));
+    }
+
+    let (old_root, extra) = match layout()? {
+        Layout::Home(dir) => (dir, Vec::new()),
+        Layout::Xdg { data, cache, config } => {
+            (data, vec![(cache, PathBuf::from("cache")), (config.join(CONFIG_FILE), PathBuf::from(CONFIG_FILE))])
+        }
+    };
+
+    if !old_root.exists() {
+        if !extra.iter().any(|(src, _)| fs::symlink_metadata(src).is_ok()) {
+            return Err(anyhow!("No GoVM installation found at {}", old_root.display()));
+        }
+        // 只有缓存或配置文件，仍然需要移动
+        fs::create_dir_all(&old_root)?;
+    }
+
+    let new_root = absolute(new_dir)?;
+    // 链接和脚本中可能是未解析符号链接的路径（例如 HOME 本身是符号链接）
+    let old_roots = vec![old_root.canonicalize()?, old_root];
+    let old_root = old_roots[0].clone();
+    if new_root == old_root {
+        return Err(anyhow!("GoVM is already installed at {}", old_root.display()));
+    }
+    if new_root.starts_with(&old_root) {
+        return Err(anyhow!("{} is inside the current GoVM directory {}", new_root.display(), old_root.display()));
+    }
+    if new_root.exists() && fs::read_dir(&new_root)?.next().is_some() {
+        return Err(anyhow!("{} already exists and is not empty", new_root.display()));
+    }
+
+    println!("{}", format!("Moving {} to {}", old_root.display(), new_root.display()).blue());
+    let size = dir_size(&old_root);
+
+    move_dir(&old_root, &new_root)?;
+    for (src, name) in &extra {
+        if fs::symlink_metadata(src).is_ok() {
+            let dst = new_root.join(name);
+            if dst.exists() {
+                println!("{}", format!("Skipping {}: {} already exists", src.display(), dst.display()).yellow());
+                continue;
+            }
+            move_dir(src, &dst)?;
+        }
+    }
+
+    let links = fix_symlinks(&new_root, &old_roots)?;
+    let scripts = fix_activation_scripts(&new_root, &old_roots)?;
+
+    println!();
+    println!("{}", format!("✅ Moved GoVM to {} ({})", new_root.display(), format_size(size)).green());
+    if links + scripts > 0 {
+        println!("Updated {} symlinks and {} activation scripts", links, scripts);
+    }
+
+    println!();
+    println!("Point GoVM at the new location and update your PATH:");
+    #[cfg(unix)]
+    {
+        println!("  export GOVM_HOME=\"{}\"", new_root.display());
+        println!("  export PATH=\"{}:$PATH\"", new_root.join("current").join("bin").display());
+    }
+    #[cfg(windows)]
+    {
+        println!("  setx GOVM_HOME \"{}\"", new_root.display());
+        println!("  PATH: {}", new_root.join("current").join("bin").display());
+    }
+    println!("Remove {} from PATH if it is still there.", old_root.join("current").join("bin").display());
+
+    Ok(())
+}
+
+/// 目标目录可能还不存在，不能直接 canonicalize
+fn absolute(path: &Path) -> Result<PathBuf> {
+    let path = if path.is_absolute() { path.to_path_buf() } else { std::env::current_dir()?.join(path) };
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if parent.exists() => Ok(parent.canonicalize()?.join(name)),
+        _ => Ok(path),
+    }
+}
+
+/// 移动文件或目录，跨文件系统时退回到复制后删除
+fn move_dir(src: &Path, dst: &Path) -> Result<()> {
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // 目标可能是已存在的空目录
+    if dst.is_dir() {
+        fs::remove_dir(dst)?;
+    }
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+
+    let copied = if src.is_dir() {
+        copy_tree(src, dst)
+    } else {
+        fs::copy(src, dst).map(|_| ()).map_err(Into::into)
+    };
+    if let Err(e) = copied {
+        // 删除复制了一半的目标（目录权限最后才设置，此时仍可写入），原目录保持不变
+        if dst.is_dir() {
+            let _ = fs::remove_dir_all(dst);
+        } else {
+            let _ = fs::remove_file(dst);
+        }
+        return Err(e.context(format!("Failed to copy {} to {}", src.display(), dst.display())));
+    }
+
+    if src.is_dir() {
+        fs::remove_dir_all(src)?;
+    } else {
+        fs::remove_file(src)?;
+    }
+    Ok(())
+}
+
+/// 复制目录树，符号链接原样复制，同一文件的多个硬链接在目标中仍然是硬链接
+/// 目录权限在所有内容复制完成后才设置（从深到浅），否则只读目录中无法创建文件
+fn copy_tree(src: &Path, dst: &Path) -> Result<()> {
+    let mut copied: HashMap<(u64, u64), PathBuf> = HashMap::new();
+    let mut dir_permissions = Vec::new();
+
+    for entry in WalkDir::new(src) {
+        let entry = entry?;
+        let dest_path = dst.join(entry.path().strip_prefix(src)?);
+        let file_type = entry.file_type();
+
+        if file_type.is_symlink() {
+            let target = fs::read_link(entry.path())?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&target, &dest_path)?;
+            #[cfg(windows)]
+            {
+                if entry.path().is_dir() {
+                    std::os::windows::fs::symlink_dir(&target, &dest_path)?;
+                } else {
+                    std::os::windows::fs::symlink_file(&target, &dest_path)?;
+                }
+            }
+        } else if file_type.is_dir() {
+            fs::create_dir_all(&dest_path)?;
+            dir_permissions.push((dest_path, entry.metadata()?.permissions()));
+        } else {
+            let metadata = entry.metadata()?;
+            match file_id(&metadata) {
+                Some(id) => {
+                    if let Some(first) = copied.get(&id) {
+                        fs::hard_link(first, &dest_path)?;
+                    } else {
+                        fs::copy(entry.path(), &dest_path)?;
+                        copied.insert(id, dest_path);
+                    }
+                }
+                None => {
+                    fs::copy(entry.path(), &dest_path)?;
+                }
+            }
+        }
+    }
+
+    for (path, permissions) in dir_permissions.into_iter().rev() {
+        fs::set_permissions(&path, permissions)?;
+    }
+
+    Ok(())
+}
+
+/// 有多个硬链接的文件的 (dev, ino)
+#[cfg(unix)]
+fn file_id(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.nlink() > 1).then(|| (metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// 将 current 和 versions 中指向原目录的符号链接改为指向新目录
+fn fix_symlinks(new_root: &Path, old_roots: &[PathBuf]) -> Result<usize> {
+    let mut fixed = 0;
+
+    for entry in WalkDir::new(new_root).max_depth(2) {
+        let entry = entry?;
+        if !entry.file_type().is_symlink() {
+            continue;
+        }
+        let target = fs::read_link(entry.path())?;
+        let Some(rest) = old_roots.iter().find_map(|r| target.strip_prefix(r).ok()) else {
+            continue;
+        };
+
+        let new_target = new_root.join(rest);
+        fs::remove_file(entry.path()).or_else(|_| fs::remove_dir(entry.path()))?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&new_target, entry.path())?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&new_target, entry.path())?;
+        fixed += 1;
+    }
+
+    Ok(fixed)
+}
+
+/// 改写激活脚本中的绝对路径
+/// Unix 上脚本位于根目录中，早期版本写在 current 指向的版本目录中
+fn fix_activation_scripts(new_root: &Path, old_roots: &[PathBuf]) -> Result<usize> {
+    let mut dirs = vec![new_root.to_path_buf(), new_root.join("current")];
+    if let Ok(entries) = fs::read_dir(new_root.join("versions")) {
+        dirs.extend(entries.flatten().map(|e| e.path()));
+    }
+
+    let new = new_root.to_string_lossy();
+    let mut fixed = 0;
+
+    for dir in dirs {
+        // 符号链接指向的目录要么已经在列表中，要么不属于 GoVM（例如导入的系统 Go）
+        if fs::symlink_metadata(&dir).map(|m| m.file_type().is_symlink()).unwrap_or(true) {
+            continue;
+        }
+        for name in ACTIVATION_SCRIPTS {
+            let path = dir.join(name);
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Some(old) = old_roots.iter().map(|r| r.to_string_lossy()).find(|r| content.contains(r.as_ref())) else {
+                continue;
+            };
+            fs::write(&path, content.replace(old.as_ref(), new.as_ref()))?;
+            fixed += 1;
+        }
+    }
+
+    Ok(fixed)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fix_links_and_scripts() {
+        let temp = tempfile::tempdir().unwrap();
+        let old_root = temp.path().join("old");
+        let new_root = temp.path().join("new");
+        let version = new_root.join("versions").join("1.22.3");
+        fs::create_dir_all(&version).unwrap();
+        std::os::unix::fs::symlink(old_root.join("versions").join("1.22.3"), new_root.join("current")).unwrap();
+        fs::write(
+            new_root.join("activate.sh"),
+            format!("export PATH=\"{}/versions/1.22.3/bin:$PATH\"\n", old_root.display()),
+        )
+        .unwrap();
+
+        let old_roots = [old_root];
+        assert_eq!(fix_symlinks(&new_root, &old_roots).unwrap(), 1);
+        assert_eq!(fix_activation_scripts(&new_root, &old_roots).unwrap(), 1);
+        assert_eq!(fs::read_link(new_root.join("current")).unwrap(), version);
+        assert!(fs::read_to_string(new_root.join("activate.sh")).unwrap().contains(&*new_root.to_string_lossy()));
+    }
+    #[test]
+    fn test_copy_tree_read_only_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        let dst = temp.path().join("dst");
+        fs::create_dir_all(src.join("pkg")).unwrap();
+        fs::write(src.join("pkg").join("a.go"), "package a\n").unwrap();
+        fs::set_permissions(src.join("pkg"), fs::Permissions::from_mode(0o555)).unwrap();
+
+        copy_tree(&src, &dst).unwrap();
+        assert!(dst.join("pkg").join("a.go").is_file());
+        assert_eq!(fs::metadata(dst.join("pkg")).unwrap().permissions().mode() & 0o777, 0o555);
+
+        for dir in [&src, &dst] {
+            fs::set_permissions(dir.join("pkg"), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+    }
+}
@@ -0,0 +1,603 @@
+use anyhow::{Result, anyhow, Context};
+use colored::Colorize;
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+
+use crate::config::http_client;
+use crate::checksum::{sha256_file, verify_sha256};
+use crate::download::{download_archive, download_base_url, fetch_releases, parse_archive_name, RemoteRelease};
+use crate::goproxy::{self, ProxyResponse, TOOLCHAIN_MODULE};
+use crate::utils::get_cache_dir;
+use crate::version::GoVersion;
+
+/// 请求头的最大长度
+const MAX_HEADER_SIZE: usize = 16 * 1024;
+
+/// 上游发行列表的缓存时间
+const UPSTREAM_TTL: Duration = Duration::from_secs(600);
+
+/// go.dev/dl JSON 中的文件条目
+#[derive(Debug, Serialize)]
+struct ReleaseFile {
+    filename: String,
+    os: String,
+    arch: String,
+    version: String,
+    sha256: String,
+    size: u64,
+    kind: String,
+}
+
+/// go.dev/dl JSON 中的版本条目
+#[derive(Debug, Serialize)]
+struct Release {
+    version: String,
+    stable: bool,
+    files: Vec<ReleaseFile>,
+}
+
+/// 解析后的 HTTP 请求
+#[derive(Debug)]
+struct Request {
+    method: String,
+    target: String,
+    range: Option<String>,
+}
+
+/// Range 请求头的解析结果
+#[derive(Debug, PartialEq, Eq)]
+enum RangeSpec {
+    /// 返回整个文件
+    Full,
+    /// 返回 [start, end] 区间（包含 end）
+    Partial(u64, u64),
+    /// 区间超出文件范围
+    Unsatisfiable,
+}
+
+/// 服务器状态
+struct ServerState {
+    cache_dir: PathBuf,
+    fetch_through: bool,
+    client: Client,
+    /// 文件名 -> (大小, 修改时间, sha256)，避免每次列表都重新计算
+    checksums: Mutex<HashMap<String, (u64, SystemTime, String)>>,
+    /// 文件名 -> 锁，避免同一文件被同时下载或生成，不同文件互不影响
+    file_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    /// 回源模式下最近一次获取的上游发行列表
+    upstream: Mutex<Option<(Instant, Arc<Vec<RemoteRelease>>)>>,
+}
+
+/// 识别发行文件名，返回版本、操作系统、架构和类型
+/// 源码包形如 "go1.21.5.src.tar.gz"，没有操作系统和架构
+fn describe_release_file(name: &str) -> Option<(GoVersion, String, String, &'static str)> {
+    if let Some(version) = name.strip_suffix(".src.tar.gz") {
+        let version = GoVersion::parse(version).ok()?;
+        return Some((version, String::new(), String::new(), "source"));
+    }
+
+    let (version, os, arch) = parse_archive_name(name)?;
+    Some((version, os, arch, "archive"))
+}
+
+/// 解析 Range 请求头，只支持单个 bytes 区间，其他形式返回整个文件
+fn parse_range(value: &str, size: u64) -> RangeSpec {
+    let spec = match value.trim().strip_prefix("bytes=") {
+        Some(spec) if !spec.contains(',') => spec.trim(),
+        _ => return RangeSpec::Full,
+    };
+
+    let (start, end) = match spec.split_once('-') {
+        Some(parts) => parts,
+        None => return RangeSpec::Full,
+    };
+
+    let range = match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+        // bytes=100-199
+        (Some(start), Some(end)) if start <= end => Some((start, end.min(size.saturating_sub(1)))),
+        // bytes=100-
+        (Some(start), None) if end.is_empty() => Some((start, size.saturating_sub(1))),
+        // bytes=-500，最后 500 字节
+        (None, Some(suffix)) if start.is_empty() && suffix > 0 => {
+            Some((size.saturating_sub(suffix), size.saturating_sub(1)))
+        }
+        _ => return RangeSpec::Full,
+    };
+
+    match range {
+        Some((start, end)) if start < size && start <= end => RangeSpec::Partial(start, end),
+        _ => RangeSpec::Unsatisfiable,
+    }
+}
+
+/// 从连接中读取并解析请求头
+async fn read_request(stream: &mut TcpStream) -> Result<Option<Request>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+
+        let mut headers = [httparse::EMPTY_HEADER; 64];
+        let mut req = httparse::Request::new(&mut headers);
+
+        match req.parse(&buf)? {
+            httparse::Status::Complete(_) => {
+                let range = req
+                    .headers
+                    .iter()
+                    .find(|h| h.name.eq_ignore_ascii_case("range"))
+                    .map(|h| String::from_utf8_lossy(h.value).to_string());
+
+                return Ok(Some(Request {
+                    method: req.method.unwrap_or("").to_string(),
+                    target: req.path.unwrap_or("/").to_string(),
+                    range,
+                }));
+            }
+            httparse::Status::Partial if buf.len() > MAX_HEADER_SIZE => {
+                return Err(anyhow!("Request header too large"));
+            }
+            httparse::Status::Partial => {}
+        }
+    }
+}
+
+/// 写入响应头
+async fn write_head(
+    stream: &mut TcpStream,
+    status: u16,
+    reason: &str,
+    headers: &[(&str, String)],
+) -> Result<()> {
+    let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason);
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("Server: govm\r\nConnection: close\r\n\r\n");
+    stream.write_all(head.as_bytes()).await?;
+    Ok(())
+}
+
+/// 写入完整的内存响应
+async fn write_response(
+    stream: &mut TcpStream,
+    method: &str,
+    status: u16,
+    reason: &str,
+    content_type: &str,
+    body: &[u8],
+) -> Result<()> {
+    let headers = [
+        ("Content-Type", content_type.to_string()),
+        ("Content-Length", body.len().to_string()),
+    ];
+    write_head(stream, status, reason, &headers).await?;
+    if method != "HEAD" {
+        stream.write_all(body).await?;
+    }
+    Ok(())
+}
+
+impl ServerState {
+    /// 获取某个文件的锁
+    async fn file_lock(&self, name: &str) -> Arc<Mutex<()>> {
+        self.file_locks.lock().await.entry(name.to_string()).or_default().clone()
+    }
+
+    /// 计算（或从内存缓存读取）缓存文件的 sha256
+    async fn checksum(&self, name: &str) -> Result<String> {
+        let path = self.cache_dir.join(name);
+        let meta = tokio::fs::metadata(&path).await?;
+        let key = (meta.len(), meta.modified()?);
+
+        if let Some((size, modified, hash)) = self.checksums.lock().await.get(name) {
+            if (*size, *modified) == key {
+                return Ok(hash.clone());
+            }
+        }
+
+        let hash = tokio::task::spawn_blocking(move || sha256_file(&path)).await??;
+        self.checksums
+            .lock()
+            .await
+            .insert(name.to_string(), (key.0, key.1, hash.clone()));
+
+        Ok(hash)
+    }
+
+    /// 回源模式下的上游发行列表，获取失败时返回 None
+    async fn upstream_releases(&self) -> Option<Arc<Vec<RemoteRelease>>> {
+        if !self.fetch_through {
+            return None;
+        }
+
+        let mut upstream = self.upstream.lock().await;
+        if let Some((fetched, releases)) = upstream.as_ref() {
+            if fetched.elapsed() < UPSTREAM_TTL {
+                return Some(releases.clone());
+            }
+        }
+
+        let releases = Arc::new(fetch_releases(&self.client).await.ok()?);
+        *upstream = Some((Instant::now(), releases.clone()));
+        Some(releases)
+    }
+
+    /// 上游公布的文件 sha256
+    async fn upstream_sha256(&self, name: &str) -> Option<String> {
+        self.upstream_releases()
+            .await?
+            .iter()
+            .flat_map(|r| &r.files)
+            .find(|f| f.filename == name && !f.sha256.is_empty())
+            .map(|f| f.sha256.clone())
+    }
+
+    /// 缓存文件与上游公布的 sha256 是否一致（没有上游信息时视为一致）
+    async fn matches_upstream(&self, name: &str) -> Result<bool> {
+        match self.upstream_sha256(name).await {
+            Some(expected) => Ok(self.checksum(name).await?.eq_ignore_ascii_case(&expected)),
+            None => Ok(true),
+        }
+    }
+
+    /// 生成与 go.dev/dl/?mode=json&include=all 兼容的列表
+    /// 只包含缓存中已有的文件，因此 include 参数不影响结果
+    async fn listing(&self) -> Result<Vec<Release>> {
+        // 版本 -> (是否稳定, 文件)
+        let mut releases: BTreeMap<GoVersion, (bool, Vec<ReleaseFile>)> = BTreeMap::new();
+        let mut entries = tokio::fs::read_dir(&self.cache_dir).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (version, os, arch, kind) = match describe_release_file(&name) {
+                Some(info) => info,
+                None => continue,
+            };
+            if !entry.file_type().await?.is_file() {
+                continue;
+            }
+            // 与上游公布的 sha256 不一致的缓存文件不提供，改为列出上游的条目
+            if !self.matches_upstream(&name).await? {
+                println!("{}", format!("Cached {} does not match the upstream checksum, ignoring it", name).yellow());
+                continue;
+            }
+
+            let file = ReleaseFile {
+                sha256: self.checksum(&name).await?,
+                size: entry.metadata().await?.len(),
+                version: version.to_download_string(),
+                filename: name,
+                os,
+                arch,
+                kind: kind.to_string(),
+            };
+            // describe_release_file 只识别正式版本（rc、beta 的文件名无法解析），上游列表中的标记优先
+            releases.entry(version).or_insert_with(|| (true, Vec::new())).1.push(file);
+        }
+
+        // 缓存未命中时可以从上游获取，列表中也包含上游的文件，
+        // 否则客户端会误以为其他平台没有构建
+        if let Some(upstream) = self.upstream_releases().await {
+            for release in upstream.iter() {
+                let version = match GoVersion::parse(&release.version) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                // 以上游标记为准
+                let (stable, files) = releases.entry(version).or_default();
+                *stable = release.stable;
+                for file in &release.files {
+                    if file.kind == "installer" || files.iter().any(|f| f.filename == file.filename) {
+                        continue;
+                    }
+                    files.push(ReleaseFile {
+                        filename: file.filename.clone(),
+                        os: file.os.clone(),
+                        arch: file.arch.clone(),
+                        version: release.version.clone(),
+                        sha256: file.sha256.clone(),
+                        size: file.size,
+                        kind: file.kind.clone(),
+                    });
+                }
+            }
+        }
+
+        // 与 go.dev 一致，按版本从新到旧排列
+        Ok(releases
+            .into_iter()
+            .rev()
+            .map(|(version, (stable, mut files))| {
+                files.sort_by(|a, b| a.filename.cmp(&b.filename));
+                Release {
+                    version: version.to_download_string(),
+                    stable,
+                    files,
+                }
+            })
+            .collect())
+    }
+
+    /// 确保文件在缓存中，必要时从上游下载
+    /// 回源模式下缓存文件必须与上游公布的 sha256 一致，否则重新下载；下载的文件校验通过后才放入缓存
+    async fn ensure_cached(&self, name: &str) -> Result<bool> {
+        let path = self.cache_dir.join(name);
+        if path.is_file() && self.matches_upstream(name).await? {
+            return Ok(true);
+        }
+        if !self.fetch_through {
+            return Ok(false);
+        }
+
+        let lock = self.file_lock(name).await;
+        let _guard = lock.lock().await;
+        if path.is_file() {
+            if self.matches_upstream(name).await? {
+                return Ok(true);
+            }
+            println!("{}", format!("Cached {} does not match the upstream checksum, fetching it again", name).yellow());
+        } else {
+            println!("{}", format!("Cache miss, fetching {} from upstream", name).blue());
+        }
+
+        let staging = tempfile::tempdir_in(&self.cache_dir)?;
+        let downloaded = download_archive(&self.client, name, staging.path()).await?;
+        if let Some(expected) = self.upstream_sha256(name).await {
+            let downloaded = downloaded.clone();
+            tokio::task::spawn_blocking(move || verify_sha256(&downloaded, &expected)).await??;
+        }
+        tokio::fs::rename(&downloaded, &path).await?;
+        Ok(true)
+    }
+}
+
+/// 发送文件，支持 Range
+async fn serve_file(stream: &mut TcpStream, request: &Request, path: &Path) -> Result<u16> {
+    let mut file = File::open(path).await?;
+    let size = file.metadata().await?.len();
+
+    let range = request
+        .range
+        .as_deref()
+        .map(|r| parse_range(r, size))
+        .unwrap_or(RangeSpec::Full);
+
+    let (status, reason, start, len) = match range {
+        RangeSpec::Full => (200, "OK", 0, size),
+        RangeSpec::Partial(start, end) => (206, "Partial Content", start, end - start + 1),
+        RangeSpec::Unsatisfiable => {
+            let headers = [
+                ("Content-Range", format!("bytes */{}", size)),
+                ("Content-Length", "0".to_string()),
+            ];
+            write_head(stream, 416, "Range Not Satisfiable", &headers).await?;
+            return Ok(416);
+        }
+    };
+
+    let mut headers = vec![
+        ("Content-Type", "application/octet-stream".to_string()),
+        ("Content-Length", len.to_string()),
+        ("Accept-Ranges", "bytes".to_string()),
+    ];
+    if status == 206 {
+        headers.push(("Content-Range", format!("bytes {}-{}/{}", start, start + len - 1, size)));
+    }
+    write_head(stream, status, reason, &headers).await?;
+
+    if request.method != "HEAD" {
+        file.seek(std::io::SeekFrom::Start(start)).await?;
+        tokio::io::copy(&mut file.take(len), stream).await?;
+    }
+
+    Ok(status)
+}
+
+/// 处理一个请求，返回状态码
+async fn handle_request(stream: &mut TcpStream, state: &ServerState, request: &Request) -> Result<u16> {
+    let method = request.method.as_str();
+    if method != "GET" && method != "HEAD" {
+        write_response(stream, method, 405, "Method Not Allowed", "text/plain", b"method not allowed\n").await?;
+        return Ok(405);
+    }
+
+    let (path, query) = request.target.split_once('?').unwrap_or((&request.target, ""));
+    // GOPROXY 协议: /golang.org/toolchain/@v/<请求>
+    if let Some(rest) = path.strip_prefix(&format!("/{}/@v/", TOOLCHAIN_MODULE)) {
+        return serve_goproxy(stream, state, request, rest).await;
+    }
+
+    // 同时支持 /<file> 和 go.dev 风格的 /dl/<file>
+    let name = path.strip_prefix("/dl").unwrap_or(path).trim_start_matches('/');
+
+    // 列表
+    if name.is_empty() {
+        if query.split('&').any(|p| p == "mode=json") {
+            let body = serde_json::to_vec_pretty(&state.listing().await?)?;
+            write_response(stream, method, 200, "OK", "application/json", &body).await?;
+        } else {
+            let mut body = String::new();
+            for release in state.listing().await? {
+                for file in release.files {
+                    body.push_str(&format!("{}  {}\n", file.sha256, file.filename));
+                }
+            }
+            write_response(stream, method, 200, "OK", "text/plain; charset=utf-8", body.as_bytes()).await?;
+        }
+        return Ok(200);
+    }
+
+    // 文件或其 .sha256
+    let (file_name, want_checksum) = match name.strip_suffix(".sha256") {
+        Some(base) => (base, true),
+        None => (name, false),
+    };
+
+    if file_name.contains(['/', '\\']) || describe_release_file(file_name).is_none() {
+        write_response(stream, method, 404, "Not Found", "text/plain", b"not found\n").await?;
+        return Ok(404);
+    }
+
+    match state.ensure_cached(file_name).await {
+        Ok(true) => {}
+        Ok(false) => {
+            write_response(stream, method, 404, "Not Found", "text/plain", b"not found\n").await?;
+            return Ok(404);
+        }
+        Err(e) => {
+            let body = format!("upstream fetch failed: {}\n", e);
+            write_response(stream, method, 502, "Bad Gateway", "text/plain", body.as_bytes()).await?;
+            return Ok(502);
+        }
+    }
+
+    if want_checksum {
+        let body = format!("{}\n", state.checksum(file_name).await?);
+        write_response(stream, method, 200, "OK", "text/plain", body.as_bytes()).await?;
+        return Ok(200);
+    }
+
+    serve_file(stream, request, &state.cache_dir.join(file_name)).await
+}
+
+/// 以 GOPROXY 协议提供 golang.org/toolchain 模块
+async fn serve_goproxy(
+    stream: &mut TcpStream,
+    state: &ServerState,
+    request: &Request,
+    rest: &str,
+) -> Result<u16> {
+    let method = request.method.as_str();
+
+    // 生成模块 zip 可能需要较长时间，按文件加锁以避免重复生成；list、.info 和 .mod 不需要加锁
+    let lock = if rest.ends_with(".zip") {
+        Some(state.file_lock(&format!("{}/{}", goproxy::MODULE_CACHE_DIR, rest)).await)
+    } else {
+        None
+    };
+    let response = {
+        let _guard = match &lock {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        let cache_dir = state.cache_dir.clone();
+        let rest = rest.to_string();
+        tokio::task::spawn_blocking(move || goproxy::handle(&cache_dir, &rest)).await??
+    };
+
+    match response {
+        ProxyResponse::Text(body, content_type) => {
+            write_response(stream, method, 200, "OK", content_type, body.as_bytes()).await?;
+            Ok(200)
+        }
+        ProxyResponse::File(path) => serve_file(stream, request, &path).await,
+        ProxyResponse::NotFound => {
+            write_response(stream, method, 404, "Not Found", "text/plain", b"not found\n").await?;
+            Ok(404)
+        }
+    }
+}
+
+/// 处理一个连接（每个连接一个请求）
+async fn handle_connection(mut stream: TcpStream, state: Arc<ServerState>) {
+    let peer = stream
+        .peer_addr()
+        .map(|a| a.to_string())
+        .unwrap_or_else(|_| "-".to_string());
+
+    let request = match read_request(&mut stream).await {
+        Ok(Some(request)) => request,
+        Ok(None) => return,
+        Err(_) => {
+            let _ = write_response(&mut stream, "GET", 400, "Bad Request", "text/plain", b"bad request\n").await;
+            return;
+        }
+    };
+
+    match handle_request(&mut stream, &state, &request).await {
+        Ok(status) => println!("{} {} {} {}", peer, request.method, request.target, status),
+        Err(e) => eprintln!("{} {} {} error: {}", peer, request.method, request.target, e),
+    }
+
+    let _ = stream.shutdown().await;
+}
+
+/// 以 go.dev/dl 兼容的方式提供缓存目录中的发行文件，
+/// 同时以 GOPROXY 协议提供 golang.org/toolchain 模块
+pub async fn serve(listen: &str, fetch_through: bool) -> Result<()> {
+    let cache_dir = get_cache_dir()?;
+    let listener = TcpListener::bind(listen)
+        .await
+        .with_context(|| format!("Failed to listen on {}", listen))?;
+
+    let state = Arc::new(ServerState {
+        cache_dir: cache_dir.clone(),
+        fetch_through,
+        client: http_client()?,
+        checksums: Mutex::new(HashMap::new()),
+        file_locks: Mutex::new(HashMap::new()),
+        upstream: Mutex::new(None),
+    });
+
+    println!("{}", format!("Serving {} on http://{}", cache_dir.display(), listener.local_addr()?).green().bold());
+    if fetch_through {
+        println!("Cache misses are fetched from {}", download_base_url());
+    }
+    println!();
+    let port = listener.local_addr()?.port();
+    println!("Point other machines at this mirror with:");
+    println!("  export GOVM_DOWNLOAD_URL=http://<this-host>:{}", port);
+    println!();
+    println!("Let GOTOOLCHAIN=auto fetch toolchains from this machine with:");
+    println!("  export GOPROXY=http://<this-host>:{},direct", port);
+    println!("  export GONOSUMDB={}", TOOLCHAIN_MODULE);
+    println!();
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(handle_connection(stream, state.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-99", 1000), RangeSpec::Partial(0, 99));
+        assert_eq!(parse_range("bytes=900-", 1000), RangeSpec::Partial(900, 999));
+        assert_eq!(parse_range("bytes=-100", 1000), RangeSpec::Partial(900, 999));
+        assert_eq!(parse_range("bytes=500-5000", 1000), RangeSpec::Partial(500, 999));
+        assert_eq!(parse_range("bytes=1000-", 1000), RangeSpec::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-1,5-6", 1000), RangeSpec::Full);
+        assert_eq!(parse_range("items=0-1", 1000), RangeSpec::Full);
+    }
+
+    #[test]
+    fn test_describe_release_file() {
+        let (version, _, _, kind) = describe_release_file("go1.21.5.src.tar.gz").unwrap();
+        assert_eq!(version, GoVersion::new(1, 21, 5));
+        assert_eq!(kind, "source");
+
+        let (_, os, arch, kind) = describe_release_file("go1.21.5.darwin-arm64.tar.gz").unwrap();
+        assert_eq!((os.as_str(), arch.as_str(), kind), ("darwin", "arm64", "archive"));
+
+        assert!(describe_release_file("go1.21.5.linux-amd64.tar.gz.download").is_none());
+        assert!(describe_release_file("go1.22rc1.linux-amd64.tar.gz").is_none());
+    }
+}
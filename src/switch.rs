@@ -2,24 +2,76 @@ use anyhow::{Result, anyhow};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::version::GoVersion;
-use crate::utils::{get_versions_dir, get_current_dir};
+use crate::download::Platform;
+use crate::install::{get_named_toolchains, toolchain_dir, version_dir};
+use crate::prune::record_last_used;
+use crate::version::{GoVersion, Toolchain};
+use crate::utils::{get_current_dir, get_govm_dir, portable_root};
 
 /// 切换 Go 版本
 /// 在 Windows 上通过创建批处理文件实现
 /// 在 Unix 系统上通过创建符号链接实现
-pub fn switch_version(version: &GoVersion) -> Result<()> {
-    let versions_dir = get_versions_dir()?;
-    let version_dir = versions_dir.join(version.to_dir_name());
+///
+/// `pin_toolchain` 为 true 时，激活脚本会设置 GOTOOLCHAIN=local，
+/// 防止 go 命令（1.21+）自动下载并运行其他版本的工具链
+pub fn switch_version(version: &GoVersion, pin_toolchain: bool) -> Result<()> {
+    switch_toolchain(version, &Platform::host(), pin_toolchain)
+}
+
+/// 切换到指定平台的 Go 版本
+/// 非当前平台的工具链需要调用方确认可以运行（例如通过 qemu-user）
+pub fn switch_toolchain(version: &GoVersion, platform: &Platform, pin_toolchain: bool) -> Result<()> {
+    let version_dir = version_dir(version, platform)?;
 
     if !version_dir.exists() {
+        let flags = if platform.is_host() {
+            String::new()
+        } else {
+            format!(" --os {} --arch {}", platform.os, platform.arch)
+        };
         return Err(anyhow!(
-            "Go {} is not installed. Run 'govm install {}' first.",
+            "Go {} ({}) is not installed. Run 'govm install {}{}' first.",
             version,
-            version
+            platform,
+            version,
+            flags
         ));
     }
 
+    activate_dir(&version_dir, pin_toolchain)?;
+
+    if platform.is_host() {
+        println!("✅ Switched to Go {}", version);
+    } else {
+        println!("✅ Switched to Go {} ({})", version, platform);
+    }
+    print_path_hint(&version_dir.join("bin"));
+
+    Ok(())
+}
+
+/// 切换到自定义名称的工具链
+pub fn switch_named(name: &str, pin_toolchain: bool) -> Result<()> {
+    let dir = toolchain_dir(&Toolchain::Named(name.to_string()))?;
+
+    if !dir.exists() {
+        return Err(anyhow!(
+            "Toolchain {} is not installed. Register it with 'govm link {} <GOROOT>' first.",
+            name,
+            name
+        ));
+    }
+
+    activate_dir(&dir, pin_toolchain)?;
+
+    println!("✅ Switched to toolchain {}", name);
+    print_path_hint(&dir.join("bin"));
+
+    Ok(())
+}
+
+/// 将 current 指向版本目录并生成激活脚本
+fn activate_dir(version_dir: &Path, pin_toolchain: bool) -> Result<()> {
     let go_bin_dir = version_dir.join("bin");
     
     if !go_bin_dir.exists() {
@@ -40,36 +92,55 @@ pub fn switch_version(version: &GoVersion) -> Result<()> {
     #[cfg(unix)]
     {
         use std::os::unix::fs::symlink;
-        symlink(&version_dir, &current_dir)?;
+        // GoVM 目录中的版本使用相对链接，整个目录移动（例如便携模式）后仍然有效
+        let target = version_dir.strip_prefix(get_govm_dir()?).unwrap_or(version_dir);
+        symlink(target, &current_dir)?;
     }
 
     #[cfg(windows)]
     {
         // Windows 使用目录链接
-        copy_dir_all(&version_dir, &current_dir)?;
+        crate::install::copy_dir_all(version_dir, &current_dir)?;
     }
 
     // 创建激活脚本
-    create_activation_script(&go_bin_dir)?;
+    create_activation_script(&go_bin_dir, pin_toolchain)?;
+
+    // 记录使用时间失败不影响切换
+    if let Some(name) = version_dir.file_name() {
+        let _ = record_last_used(&name.to_string_lossy());
+    }
+
+    Ok(())
+}
 
-    println!("✅ Switched to Go {}", version);
+/// 提示将 bin 目录加入 PATH
+fn print_path_hint(go_bin_dir: &Path) {
     println!("\nNote: Add the following to your PATH to use this version:");
-    
+    println!("  {}", go_bin_dir.display());
+
+    let current_bin = get_current_dir().map(|d| d.join("bin")).unwrap_or_default();
+    println!("\nOr add to your shell profile:");
+
     #[cfg(windows)]
-    {
-        println!("  {}", go_bin_dir.display());
-        println!("\nOr add to your shell profile:");
-        println!("  Windows: %LOCALAPPDATA%\\govm\\current\\bin");
-    }
-    
+    println!("  Windows: {}", current_bin.display());
+
     #[cfg(unix)]
-    {
-        println!("  {}", go_bin_dir.display());
-        println!("\nOr add to your shell profile:");
-        println!("  export PATH=\"$HOME/.govm/current/bin:$PATH\"");
-    }
+    println!("  export PATH=\"{}:$PATH\"", current_bin.display());
+}
 
-    Ok(())
+/// 获取当前激活的自定义工具链名称
+/// Unix 上根据 current 链接的目标判断；Windows 上 current 是复制的目录，无法区分
+pub fn get_current_named() -> Result<Option<String>> {
+    let current_dir = get_current_dir()?;
+    let target = match fs::read_link(&current_dir) {
+        Ok(target) => target,
+        Err(_) => return Ok(None),
+    };
+
+    let name = target.file_name().map(|n| n.to_string_lossy().to_string());
+    let named = get_named_toolchains()?;
+    Ok(name.filter(|n| named.contains(n)))
 }
 
 /// 获取当前激活的 Go 版本
@@ -94,6 +165,7 @@ pub fn get_current_version() -> Result<Option<GoVersion>> {
     // 尝试执行 go version
     let output = std::process::Command::new(&go_bin)
         .arg("version")
+        .env("GOTOOLCHAIN", "local")
         .output();
 
     match output {
@@ -111,22 +183,58 @@ pub fn get_current_version() -> Result<Option<GoVersion>> {
     }
 }
 
+/// 激活脚本中 bin 目录的写法
+/// 便携模式下（`portable_dir` 为 GoVM 目录）位于 GoVM 目录中时写成相对于 `root`（脚本所在位置的表达式）的路径，
+/// 这样整个目录移动或挂载到其他位置后脚本仍然有效；其他情况写绝对路径
+fn script_bin_dir(go_bin_dir: &Path, portable_dir: Option<&Path>, root: &str, separator: char) -> String {
+    match portable_dir.and_then(|dir| go_bin_dir.strip_prefix(dir).ok()) {
+        Some(relative) => {
+            let parts: Vec<String> = relative.iter().map(|p| p.to_string_lossy().to_string()).collect();
+            format!("{}{}{}", root, separator, parts.join(&separator.to_string()))
+        }
+        _ => go_bin_dir.display().to_string(),
+    }
+}
+
 /// 创建激活脚本
-fn create_activation_script(go_bin_dir: &Path) -> Result<()> {
-    let current_dir = get_current_dir()?;
-    
+/// Unix 上 current 是指向版本目录的符号链接，脚本写在 GoVM 目录中，
+/// 避免写进版本目录（共享目录中的版本属于所有用户）
+fn create_activation_script(go_bin_dir: &Path, pin_toolchain: bool) -> Result<()> {
+    let govm_dir = get_govm_dir()?;
+    let portable_dir = portable_root().map(|_| govm_dir.clone());
+
     #[cfg(unix)]
     {
         // 创建 shell 脚本
-        let script_path = current_dir.join("activate.sh");
+        let script_path = govm_dir.join("activate.sh");
+        let toolchain_line = if pin_toolchain {
+            "export GOTOOLCHAIN=local\n"
+        } else {
+            ""
+        };
+        let bin_dir = script_bin_dir(go_bin_dir, portable_dir.as_deref(), "$_govm_root", '/');
+        // 便携模式需要脚本自身的位置；BASH_SOURCE 放在 eval 中，避免 dash 等 POSIX sh 解析时报错
+        let root_lines = if bin_dir.starts_with("$_govm_root") {
+            concat!(
+                "if [ -n \"${BASH_VERSION:-}\" ]; then eval '_govm_script=${BASH_SOURCE[0]}'; else _govm_script=\"$0\"; fi\n",
+                "_govm_root=\"$(cd \"$(dirname \"$_govm_script\")\" && pwd)\"\n",
+            )
+        } else {
+            ""
+        };
+        let cleanup_line = if root_lines.is_empty() { "" } else { "unset _govm_script _govm_root\n" };
         let script = format!(
-            r#"#!/bin/bash
+            r#"#!/bin/sh
 # GoVM activation script
-export PATH="{}:$PATH"
-echo "Go environment activated. Go binary: $(which go)"
+{}export PATH="{}:$PATH"
+{}unset GOROOT
+{}echo "Go environment activated. Go binary: $(command -v go)"
 go version
 "#,
-            go_bin_dir.display()
+            root_lines,
+            bin_dir,
+            cleanup_line,
+            toolchain_line
         );
         fs::write(&script_path, script)?;
         
@@ -142,28 +250,44 @@ go version
 
     #[cfg(windows)]
     {
-        // 创建批处理文件
+        let current_dir = get_current_dir()?;
+
+        // 创建批处理文件，%~dp0 为脚本所在的 current 目录（以 \ 结尾）
         let script_path = current_dir.join("activate.bat");
+        let toolchain_line = if pin_toolchain {
+            "set \"GOTOOLCHAIN=local\"\n"
+        } else {
+            ""
+        };
         let script = format!(
             r#"@echo off
 REM GoVM activation script
 set "PATH={};%PATH%"
-echo Go environment activated.
+set "GOROOT="
+{}echo Go environment activated.
 go version
 "#,
-            go_bin_dir.display()
+            script_bin_dir(go_bin_dir, portable_dir.as_deref(), "%~dp0..", '\\'),
+            toolchain_line
         );
         fs::write(&script_path, script)?;
 
         // 创建 PowerShell 脚本
         let ps_script_path = current_dir.join("activate.ps1");
+        let ps_toolchain_line = if pin_toolchain {
+            "$env:GOTOOLCHAIN = \"local\"\n"
+        } else {
+            ""
+        };
         let ps_script = format!(
             r#"# GoVM activation script
 $env:PATH = "{};" + $env:PATH
-Write-Host "Go environment activated." -ForegroundColor Green
+Remove-Item Env:GOROOT -ErrorAction SilentlyContinue
+{}Write-Host "Go environment activated." -ForegroundColor Green
 & go version
 "#,
-            go_bin_dir.display()
+            script_bin_dir(go_bin_dir, portable_dir.as_deref(), "$PSScriptRoot\\..", '\\'),
+            ps_toolchain_line
         );
         fs::write(&ps_script_path, ps_script)?;
     }
@@ -172,23 +296,29 @@ Write-Host "Go environment activated." -ForegroundColor Green
 }
 
 /// 打印环境变量设置命令
-pub fn print_env_commands(version: &GoVersion) -> Result<()> {
-    let versions_dir = get_versions_dir()?;
-    let version_dir = versions_dir.join(version.to_dir_name());
-    let go_bin_dir = version_dir.join("bin");
+pub fn print_env_commands(toolchain: &Toolchain, pin_toolchain: bool) -> Result<()> {
+    let go_bin_dir = toolchain_dir(toolchain)?.join("bin");
 
     if !go_bin_dir.exists() {
-        return Err(anyhow!("Go {} is not installed", version));
+        return Err(anyhow!("Go {} is not installed", toolchain));
     }
 
     #[cfg(unix)]
     {
         println!("export PATH=\"{}:$PATH\"", go_bin_dir.display());
+        println!("unset GOROOT");
+        if pin_toolchain {
+            println!("export GOTOOLCHAIN=local");
+        }
     }
 
     #[cfg(windows)]
     {
         println!("$env:PATH = \"{};$env:PATH\"", go_bin_dir.display());
+        println!("Remove-Item Env:GOROOT -ErrorAction SilentlyContinue");
+        if pin_toolchain {
+            println!("$env:GOTOOLCHAIN = \"local\"");
+        }
     }
 
     Ok(())
@@ -206,23 +336,29 @@ pub fn get_current_go_path() -> Result<Option<PathBuf>> {
     }
 }
 
-/// 复制目录（用于 Windows，因为 Windows 的符号链接需要管理员权限）
-#[cfg(windows)]
-fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
-    fs::create_dir_all(&dst)?;
-    
-    for entry in walkdir::WalkDir::new(&src) {
-        let entry = entry?;
-        let path = entry.path();
-        let relative = path.strip_prefix(&src)?;
-        let dest_path = dst.as_ref().join(relative);
-
-        if path.is_dir() {
-            fs::create_dir_all(&dest_path)?;
-        } else {
-            fs::copy(path, dest_path)?;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_bin_dir() {
+        let govm_dir = Path::new("/media/usb/govm");
+        assert_eq!(
+            script_bin_dir(&govm_dir.join("versions/1.22.3/bin"), Some(govm_dir), "$_govm_root", '/'),
+            "$_govm_root/versions/1.22.3/bin"
+        );
+        assert_eq!(
+            script_bin_dir(&govm_dir.join("versions/1.22.3/bin"), Some(govm_dir), "%~dp0..", '\\'),
+            "%~dp0..\\versions\\1.22.3\\bin"
+        );
+        assert_eq!(
+            script_bin_dir(Path::new("/opt/govm/versions/1.22.3/bin"), Some(govm_dir), "$_govm_root", '/'),
+            "/opt/govm/versions/1.22.3/bin"
+        );
+        // 非便携模式始终写绝对路径
+        assert_eq!(
+            script_bin_dir(&govm_dir.join("versions/1.22.3/bin"), None, "$_govm_root", '/'),
+            "/media/usb/govm/versions/1.22.3/bin"
+        );
     }
-    
-    Ok(())
 }
@@ -1,8 +1,10 @@
 use anyhow::{Result, anyhow};
+use colored::Colorize;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use crate::goproxy::ToolchainModule;
 use crate::version::GoVersion;
 
 /// 系统安装的 Go 信息
@@ -12,6 +14,8 @@ pub struct SystemGo {
     pub path: PathBuf,
     pub bin_path: PathBuf,
     pub source: GoSource,
+    /// 判断来源的依据（包数据库记录、安装标记文件等）
+    pub evidence: String,
 }
 
 /// Go 安装来源
@@ -19,8 +23,24 @@ pub struct SystemGo {
 pub enum GoSource {
     /// 从官网下载的发行版
     Official,
-    /// 包管理器安装 (brew, apt, yum 等)
-    PackageManager,
+    /// Debian/Ubuntu 软件包 (apt/dpkg)
+    Dpkg,
+    /// Fedora/RHEL/openSUSE 软件包 (dnf/yum/zypper)
+    Rpm,
+    /// Arch Linux 软件包
+    Pacman,
+    /// Snap 软件包
+    Snap,
+    /// Nix store
+    Nix,
+    /// Homebrew (macOS 和 Linux)
+    Homebrew,
+    /// golang.org/dl 下载的版本 (~/sdk/go1.x.y)
+    GolangDl,
+    /// GOTOOLCHAIN 下载到模块缓存中的工具链
+    ModCache,
+    /// 其他 Go 版本管理工具安装的版本
+    OtherManager(Manager),
     /// 未知来源
     Unknown,
 }
@@ -29,17 +49,80 @@ impl std::fmt::Display for GoSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             GoSource::Official => write!(f, "official"),
-            GoSource::PackageManager => write!(f, "package manager"),
+            GoSource::Dpkg => write!(f, "apt/dpkg"),
+            GoSource::Rpm => write!(f, "rpm"),
+            GoSource::Pacman => write!(f, "pacman"),
+            GoSource::Snap => write!(f, "snap"),
+            GoSource::Nix => write!(f, "nix"),
+            GoSource::Homebrew => write!(f, "homebrew"),
+            GoSource::GolangDl => write!(f, "golang.org/dl"),
+            GoSource::ModCache => write!(f, "go module cache"),
+            GoSource::OtherManager(manager) => write!(f, "{}", manager),
             GoSource::Unknown => write!(f, "unknown"),
         }
     }
 }
 
+/// 其他 Go 版本管理工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Manager {
+    /// moovweb/gvm (~/.gvm/gos)
+    Gvm,
+    /// syndbg/goenv (~/.goenv/versions)
+    Goenv,
+    /// asdf-golang (~/.asdf/installs/golang)
+    Asdf,
+    /// voidint/g (~/.g/versions)
+    G,
+    /// golang.org/dl (~/sdk)
+    Sdk,
+}
+
+impl std::fmt::Display for Manager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Manager::Gvm => write!(f, "gvm"),
+            Manager::Goenv => write!(f, "goenv"),
+            Manager::Asdf => write!(f, "asdf"),
+            Manager::G => write!(f, "g"),
+            Manager::Sdk => write!(f, "golang.org/dl"),
+        }
+    }
+}
+
+impl Manager {
+    /// 工具的根目录，优先使用工具自己的环境变量
+    pub fn root_dir(&self, home: &Path) -> PathBuf {
+        let from_env = |name: &str| env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+
+        match self {
+            Manager::Gvm => from_env("GVM_ROOT").unwrap_or_else(|| home.join(".gvm")),
+            Manager::Goenv => from_env("GOENV_ROOT").unwrap_or_else(|| home.join(".goenv")),
+            Manager::Asdf => from_env("ASDF_DATA_DIR").unwrap_or_else(|| home.join(".asdf")),
+            Manager::G => from_env("G_HOME").unwrap_or_else(|| home.join(".g")),
+            Manager::Sdk => home.join("sdk"),
+        }
+    }
+
+    /// 存放各个版本的目录
+    pub fn versions_dir(&self, home: &Path) -> PathBuf {
+        let root = self.root_dir(home);
+        match self {
+            Manager::Gvm => root.join("gos"),
+            Manager::Goenv => root.join("versions"),
+            Manager::Asdf => root.join("installs").join("golang"),
+            Manager::G => root.join("versions"),
+            Manager::Sdk => root,
+        }
+    }
+}
+
 /// 检测系统中是否已安装 Go
 pub fn detect_system_go() -> Result<Option<SystemGo>> {
     // 尝试执行 go version
     let output = Command::new("go")
         .arg("version")
+        .env("GOTOOLCHAIN", "local")
         .output();
 
     match output {
@@ -51,36 +134,45 @@ pub fn detect_system_go() -> Result<Option<SystemGo>> {
     }
 }
 
-/// 解析 go version 的输出
-fn parse_go_version_output(output: &str) -> Result<Option<SystemGo>> {
-    // 输出格式: go version go1.21.5 linux/amd64
-    let parts: Vec<&str> = output.trim().split_whitespace().collect();
-    
+/// 解析 go version 输出中的版本号
+/// 输出格式: go version go1.21.5 linux/amd64
+fn parse_version_line(output: &str) -> Option<GoVersion> {
+    let parts: Vec<&str> = output.split_whitespace().collect();
+
     if parts.len() < 3 || parts[0] != "go" || parts[1] != "version" {
-        return Ok(None);
+        return None;
     }
 
-    let version_str = parts[2]; // e.g., "go1.21.5"
-    let version = match GoVersion::parse(version_str) {
-        Ok(v) => v,
-        Err(_) => return Ok(None),
+    // e.g., "go1.21.5"
+    GoVersion::parse(parts[2]).ok()
+}
+
+/// 解析 go version 的输出
+fn parse_go_version_output(output: &str) -> Result<Option<SystemGo>> {
+    let version = match parse_version_line(output) {
+        Some(v) => v,
+        None => return Ok(None),
     };
 
     // 获取 go 可执行文件的路径
     let bin_path = get_go_binary_path()?;
-    let path = bin_path.parent()
+
+    // /usr/bin/go 等通常是指向真实 GOROOT 的符号链接
+    let resolved = bin_path.canonicalize().unwrap_or_else(|_| bin_path.clone());
+    let path = resolved.parent()
         .and_then(|p| p.parent())
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| bin_path.clone());
 
     // 检测来源
-    let source = detect_go_source(&path);
+    let (source, evidence) = detect_go_source(&path);
 
     Ok(Some(SystemGo {
         version,
         path,
         bin_path,
         source,
+        evidence,
     }))
 }
 
@@ -125,36 +217,339 @@ fn get_go_binary_path() -> Result<PathBuf> {
     Err(anyhow!("Could not find go binary in PATH"))
 }
 
-/// 检测 Go 安装的来源
-fn detect_go_source(go_root: &Path) -> GoSource {
-    let path_str = go_root.to_string_lossy().to_lowercase();
+/// 当前平台的 go 可执行文件名
+pub fn go_exe_name() -> &'static str {
+    if cfg!(windows) {
+        "go.exe"
+    } else {
+        "go"
+    }
+}
 
-    // 检测常见的包管理器安装路径
-    #[cfg(target_os = "macos")]
-    if path_str.contains("homebrew") || path_str.contains("opt/go") || path_str.contains("cellar") {
-        return GoSource::PackageManager;
+/// 运行 GOROOT 下的 bin/go version，确认这是一个可用的 Go 安装
+pub fn probe_goroot(goroot: &Path) -> Option<SystemGo> {
+    let bin_path = goroot.join("bin").join(go_exe_name());
+    if !bin_path.is_file() {
+        return None;
     }
 
-    #[cfg(target_os = "linux")]
-    if path_str.contains("/usr/lib/go") || 
-       path_str.contains("/usr/local/go") && path_str != "/usr/local/go" {
-        return GoSource::PackageManager;
+    let output = Command::new(&bin_path)
+        .arg("version")
+        .env("GOTOOLCHAIN", "local")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = parse_version_line(&String::from_utf8_lossy(&output.stdout))?;
+    let (source, evidence) = detect_go_source(goroot);
+
+    Some(SystemGo {
+        version,
+        path: goroot.to_path_buf(),
+        bin_path,
+        source,
+        evidence,
+    })
+}
+
+/// 常见的 Go 安装位置
+fn well_known_goroots() -> Vec<PathBuf> {
+    well_known_goroots_under(Path::new("/"), dirs::home_dir().as_deref())
+}
+
+/// 以 root 为文件系统根、home 为用户目录列出常见的 Go 安装位置
+fn well_known_goroots_under(root: &Path, home: Option<&Path>) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // 列出 dir 下名称以 prefix 开头的子目录
+    let children_with_prefix = |dir: &Path, prefix: &str| -> Vec<PathBuf> {
+        let mut found: Vec<PathBuf> = std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+            .map(|e| e.path())
+            .collect();
+        found.sort();
+        found
+    };
+
+    #[cfg(unix)]
+    {
+        candidates.push(root.join("usr/local/go"));
+        candidates.push(root.join("usr/lib/go"));
+        candidates.push(root.join("usr/lib/golang"));
+        candidates.extend(children_with_prefix(&root.join("usr/lib"), "go-"));
+        candidates.push(root.join("snap/go/current"));
+        candidates.push(root.join("home/linuxbrew/.linuxbrew/opt/go/libexec"));
+        candidates.push(root.join("opt/homebrew/opt/go/libexec"));
+        candidates.push(root.join("usr/local/opt/go/libexec"));
+        candidates.push(root.join("opt/go"));
     }
 
     #[cfg(windows)]
-    if path_str.contains("\\program files\\go") || 
-       path_str.contains("\\program files (x86)\\go") {
-        return GoSource::PackageManager;
+    {
+        let _ = root;
+        candidates.push(PathBuf::from("C:\\Go"));
+        if let Some(program_files) = env::var_os("ProgramFiles") {
+            candidates.push(PathBuf::from(program_files).join("Go"));
+        }
+        if let Some(program_files) = env::var_os("ProgramFiles(x86)") {
+            candidates.push(PathBuf::from(program_files).join("Go"));
+        }
+    }
+
+    if let Some(home) = home {
+        #[cfg(unix)]
+        candidates.push(home.join(".linuxbrew/opt/go/libexec"));
+
+        // golang.org/dl 下载的版本: ~/sdk/go1.x.y
+        candidates.extend(children_with_prefix(&home.join("sdk"), "go"));
+    }
+
+    candidates
+}
+
+/// 扫描本机所有的 Go 安装（常见位置和 PATH 中的每一项），不包括 GoVM 管理的版本
+pub fn discover_system_gos() -> Result<Vec<SystemGo>> {
+    let govm_dir = crate::utils::get_govm_dir()?;
+    let path = env::var_os("PATH").unwrap_or_default();
+    Ok(discover_gos(&path, well_known_goroots(), &govm_dir))
+}
+
+/// 依次探测 PATH 中 go 所在的 GOROOT 和 extra 中的候选目录，按真实路径去重
+fn discover_gos(path: &std::ffi::OsStr, extra: Vec<PathBuf>, govm_dir: &Path) -> Vec<SystemGo> {
+    let govm_dir = govm_dir.canonicalize().unwrap_or_else(|_| govm_dir.to_path_buf());
+
+    let mut candidates = Vec::new();
+
+    // PATH 中的 go，按顺序排在前面
+    for dir in env::split_paths(path) {
+        let go_path = dir.join(go_exe_name());
+        if !go_path.is_file() {
+            continue;
+        }
+        // /usr/bin/go 等通常是指向真实 GOROOT 的符号链接
+        let resolved = go_path.canonicalize().unwrap_or(go_path);
+        if let Some(goroot) = resolved.parent().and_then(|p| p.parent()) {
+            candidates.push(goroot.to_path_buf());
+        }
     }
 
-    // 如果路径是标准的 /usr/local/go 或 C:\Go，可能是官方安装包
-    if path_str == "/usr/local/go" || 
-       path_str == "c:\\go" ||
-       path_str.ends_with("\\go") && !path_str.contains("govm") {
-        return GoSource::Official;
+    candidates.extend(extra);
+
+    let mut seen = Vec::new();
+    let mut found = Vec::new();
+
+    for candidate in candidates {
+        let canonical = match candidate.canonicalize() {
+            Ok(p) => p,
+            Err(_) => continue,
+        };
+
+        if canonical.starts_with(&govm_dir) || seen.contains(&canonical) {
+            continue;
+        }
+        seen.push(canonical);
+
+        if let Some(go) = probe_goroot(&candidate) {
+            found.push(go);
+        }
     }
 
-    GoSource::Unknown
+    found
+}
+
+/// 判断路径是否位于某个版本管理工具的版本目录中
+/// golang.org/dl 的 ~/sdk 由 .unpacked-success 标记单独识别，这里不包括
+fn manager_owning(path: &Path) -> Option<Manager> {
+    let home = dirs::home_dir()?;
+
+    [Manager::Gvm, Manager::Goenv, Manager::Asdf, Manager::G]
+        .into_iter()
+        .find(|m| {
+            let store = m.versions_dir(&home);
+            let store = store.canonicalize().unwrap_or(store);
+            path.starts_with(store)
+        })
+}
+
+/// 根据路径特征判断来源（这些位置由对应工具独占）
+fn detect_source_by_path(go_root: &Path) -> Option<(GoSource, String)> {
+    let components: Vec<String> = go_root
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let has_pair = |a: &str, b: &str| components.windows(2).any(|w| w[0] == a && w[1] == b);
+
+    if let Some(module) = components.iter().find(|c| c.starts_with("toolchain@v0.0.1-")) {
+        return Some((GoSource::ModCache, format!("module golang.org/{}", module)));
+    }
+
+    if go_root.starts_with("/nix/store") {
+        let store_path: PathBuf = go_root.components().take(4).collect();
+        return Some((GoSource::Nix, format!("Nix store path {}", store_path.display())));
+    }
+
+    if go_root.starts_with("/snap") {
+        let revision: PathBuf = go_root.components().take(4).collect();
+        return Some((GoSource::Snap, format!("snap mount {}", revision.display())));
+    }
+
+    if has_pair("Cellar", "go") {
+        let keg: PathBuf = go_root
+            .ancestors()
+            .find(|p| p.parent().and_then(|p| p.file_name()) == Some("go".as_ref()))
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| go_root.to_path_buf());
+        let receipt = keg.join("INSTALL_RECEIPT.json");
+        let evidence = if receipt.exists() {
+            format!("Homebrew receipt {}", receipt.display())
+        } else {
+            format!("Homebrew keg {}", keg.display())
+        };
+        return Some((GoSource::Homebrew, evidence));
+    }
+
+    None
+}
+
+/// 判断 dpkg 的 .list 或 pacman 的 files 内容中是否包含指定文件
+/// pacman 记录的路径没有开头的 '/'
+fn file_list_contains(content: &str, target: &str) -> bool {
+    let relative = target.trim_start_matches('/');
+    content.lines().any(|l| {
+        let l = l.trim();
+        l == target || l == relative
+    })
+}
+
+/// 查询 dpkg 数据库中拥有该文件的软件包
+fn find_dpkg_owner(targets: &[String]) -> Option<String> {
+    let info_dir = Path::new("/var/lib/dpkg/info");
+    let entries = std::fs::read_dir(info_dir).ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        // 只检查 Go 相关的软件包，如 golang-1.22-go.list
+        if !name.ends_with(".list") || !name.contains("go") {
+            continue;
+        }
+        let content = match std::fs::read_to_string(entry.path()) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        if targets.iter().any(|t| file_list_contains(&content, t)) {
+            let package = name.trim_end_matches(".list");
+            return Some(format!("owned by dpkg package {} ({})", package, entry.path().display()));
+        }
+    }
+
+    None
+}
+
+/// 查询 pacman 数据库中拥有该文件的软件包
+fn find_pacman_owner(targets: &[String]) -> Option<String> {
+    let local_dir = Path::new("/var/lib/pacman/local");
+    let entries = std::fs::read_dir(local_dir).ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with("go") {
+            continue;
+        }
+        let files = entry.path().join("files");
+        let content = match std::fs::read_to_string(&files) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        if targets.iter().any(|t| file_list_contains(&content, t)) {
+            return Some(format!("owned by pacman package {} ({})", name, files.display()));
+        }
+    }
+
+    None
+}
+
+/// 查询 rpm 数据库中拥有该文件的软件包
+fn find_rpm_owner(targets: &[String]) -> Option<String> {
+    if !Path::new("/var/lib/rpm").exists() && !Path::new("/usr/lib/sysimage/rpm").exists() {
+        return None;
+    }
+
+    for target in targets {
+        let output = Command::new("rpm")
+            .args(["-qf", target])
+            .output()
+            .ok()?;
+
+        if output.status.success() {
+            let package = String::from_utf8_lossy(&output.stdout);
+            let package = package.lines().next().unwrap_or("").trim();
+            if !package.is_empty() {
+                return Some(format!("owned by rpm package {} ('rpm -qf {}')", package, target));
+            }
+        }
+    }
+
+    None
+}
+
+/// 检测 Go 安装的来源，返回来源和判断依据
+fn detect_go_source(go_root: &Path) -> (GoSource, String) {
+    let canonical = go_root.canonicalize().unwrap_or_else(|_| go_root.to_path_buf());
+
+    if let Some(found) = detect_source_by_path(&canonical) {
+        return found;
+    }
+
+    // golang.org/dl 解压完成后会写入 .unpacked-success 标记
+    let marker = canonical.join(".unpacked-success");
+    if marker.exists() {
+        return (GoSource::GolangDl, format!("marker file {}", marker.display()));
+    }
+
+    if let Some(manager) = manager_owning(&canonical) {
+        return (
+            GoSource::OtherManager(manager),
+            format!("inside {}'s version store", manager),
+        );
+    }
+
+    // 查询包管理器数据库
+    let go_bin = Path::new("bin").join(go_exe_name());
+    let mut targets = vec![canonical.join(&go_bin).to_string_lossy().to_string()];
+    let original = go_root.join(&go_bin).to_string_lossy().to_string();
+    if !targets.contains(&original) {
+        targets.push(original);
+    }
+
+    if let Some(evidence) = find_dpkg_owner(&targets) {
+        return (GoSource::Dpkg, evidence);
+    }
+    if let Some(evidence) = find_pacman_owner(&targets) {
+        return (GoSource::Pacman, evidence);
+    }
+    if let Some(evidence) = find_rpm_owner(&targets) {
+        return (GoSource::Rpm, evidence);
+    }
+
+    // 没有包管理器认领，且带有发行版的 VERSION 文件，视为官方安装包
+    let version_file = canonical.join("VERSION");
+    if version_file.exists() {
+        return (
+            GoSource::Official,
+            format!("{} present, not owned by any package database", version_file.display()),
+        );
+    }
+
+    (
+        GoSource::Unknown,
+        format!("no package database or marker file claims {}", canonical.display()),
+    )
 }
 
 /// 检查系统 Go 是否在 PATH 中优先级高于 GoVM
@@ -205,74 +600,332 @@ pub fn is_system_go_first_in_path() -> Result<bool> {
     Ok(found_system && !found_govm)
 }
 
-/// 导入系统安装的 Go 到 GoVM
-pub fn import_system_go() -> Result<PathBuf> {
-    let system_go = detect_system_go()?.ok_or_else(|| {
-        anyhow!("No system Go installation found")
-    })?;
+/// 导入方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMode {
+    /// 创建指向原安装目录的符号链接
+    Link,
+    /// 复制整个安装目录，原安装被卸载后仍可使用
+    Copy,
+}
+
+impl ImportMode {
+    /// 平台默认的导入方式
+    /// Windows 上创建目录链接需要管理员权限，因此默认复制
+    pub fn platform_default() -> Self {
+        if cfg!(windows) {
+            ImportMode::Copy
+        } else {
+            ImportMode::Link
+        }
+    }
+}
 
-    println!("Found system Go:", );
-    println!("  Version: {}", system_go.version);
-    println!("  Location: {}", system_go.path.display());
-    println!("  Source: {}", system_go.source);
+/// 将一个 Go 安装导入到 GoVM 的版本目录
+pub fn import_go(go: &SystemGo, mode: ImportMode) -> Result<PathBuf> {
+    println!("Found Go:");
+    println!("  Version: {}", go.version);
+    println!("  Location: {}", go.path.display());
+    println!("  Source: {}", go.source);
 
     // 检查是否已经在 GoVM 中
     let versions_dir = crate::utils::get_versions_dir()?;
-    let target_dir = versions_dir.join(system_go.version.to_dir_name());
+    let target_dir = versions_dir.join(go.version.to_dir_name());
 
     if target_dir.exists() {
         return Err(anyhow!(
             "Go {} is already managed by GoVM at {}",
-            system_go.version,
+            go.version,
             target_dir.display()
         ));
     }
 
-    // 创建符号链接或复制文件
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::symlink;
-        symlink(&system_go.path, &target_dir)?;
-        println!("✅ Imported Go {} as symlink", system_go.version);
-    }
+    // 链接到真实目录，避免出现链接指向链接
+    let source = go.path.canonicalize().unwrap_or_else(|_| go.path.clone());
 
-    #[cfg(windows)]
-    {
-        // Windows 上创建目录连接需要管理员权限，这里改为复制
-        println!("Copying files from {} to {}...", 
-            system_go.path.display(), 
+    if mode == ImportMode::Copy {
+        println!("Copying files from {} to {}...",
+            source.display(),
             target_dir.display()
         );
-        copy_dir_all(&system_go.path, &target_dir)?;
-        println!("✅ Imported Go {} (copied)", system_go.version);
+    }
+    import_to(&source, &target_dir, mode)?;
+
+    match mode {
+        ImportMode::Link => println!("✅ Imported Go {} as symlink", go.version),
+        ImportMode::Copy => println!("✅ Imported Go {} (copied)", go.version),
     }
 
     Ok(target_dir)
 }
 
-/// 递归复制目录
-#[cfg(windows)]
-fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> Result<()> {
-    use walkdir::WalkDir;
-    
-    std::fs::create_dir_all(&dst)?;
-    
-    for entry in WalkDir::new(&src) {
-        let entry = entry?;
-        let path = entry.path();
-        let relative = path.strip_prefix(&src)?;
-        let dest_path = dst.as_ref().join(relative);
+/// 以链接或复制的方式把 GOROOT 放到版本目录，复制失败时删除不完整的目录
+fn import_to(source: &Path, target_dir: &Path, mode: ImportMode) -> Result<()> {
+    match mode {
+        ImportMode::Link => {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(source, target_dir)?;
+
+            #[cfg(windows)]
+            std::os::windows::fs::symlink_dir(source, target_dir).map_err(|e| {
+                anyhow!(
+                    "Failed to create directory link ({}). Try '--mode copy' or run as administrator.",
+                    e
+                )
+            })?;
+        }
+        ImportMode::Copy => {
+            let skipped = match copy_resolving_links(source, target_dir) {
+                Ok(skipped) => skipped,
+                Err(e) => {
+                    let _ = std::fs::remove_dir_all(target_dir);
+                    return Err(e);
+                }
+            };
+            for link in &skipped {
+                println!("{}", format!("Warning: skipped dangling symlink {}", link.display()).yellow());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 复制 GOROOT，符号链接会被解析并复制其内容，使复制的结果不依赖原安装，
+/// 例如 Debian 的 /usr/lib/go-1.xx 中 src 等目录链接到 /usr/share/go-1.xx
+/// 返回跳过的失效链接
+fn copy_resolving_links(src: &Path, dst: &Path) -> Result<Vec<PathBuf>> {
+    let mut skipped = Vec::new();
+    std::fs::create_dir_all(dst)?;
 
-        if path.is_dir() {
+    for entry in walkdir::WalkDir::new(src).follow_links(true) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) if e.loop_ancestor().is_none() && e.path().is_some_and(|p| !p.exists() && p.is_symlink()) => {
+                skipped.extend(e.path().map(Path::to_path_buf));
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let dest_path = dst.join(entry.path().strip_prefix(src)?);
+
+        if entry.file_type().is_dir() {
             std::fs::create_dir_all(&dest_path)?;
         } else {
-            std::fs::copy(path, dest_path)?;
+            std::fs::copy(entry.path(), dest_path)?;
         }
     }
-    
+
+    Ok(skipped)
+}
+
+/// 导入 PATH 中的系统 Go 到 GoVM
+pub fn import_system_go(mode: ImportMode) -> Result<PathBuf> {
+    let system_go = detect_system_go()?.ok_or_else(|| {
+        anyhow!("No system Go installation found in PATH. Use '--path <GOROOT>' or '--all'.")
+    })?;
+
+    import_go(&system_go, mode)
+}
+
+/// 导入指定 GOROOT 的 Go 到 GoVM
+pub fn import_from_path(goroot: &Path, mode: ImportMode) -> Result<PathBuf> {
+    if !goroot.is_dir() {
+        return Err(anyhow!("Directory not found: {}", goroot.display()));
+    }
+
+    let go = probe_goroot(goroot).ok_or_else(|| {
+        anyhow!(
+            "{} is not a valid GOROOT: '{}' did not report a Go version",
+            goroot.display(),
+            goroot.join("bin").join(go_exe_name()).display()
+        )
+    })?;
+
+    import_go(&go, mode)
+}
+
+/// 导入本机发现的所有 Go 安装，跳过已被 GoVM 管理的版本
+pub fn import_all(mode: ImportMode) -> Result<Vec<PathBuf>> {
+    let installations = discover_system_gos()?;
+
+    if installations.is_empty() {
+        println!("No system Go installation found.");
+        return Ok(Vec::new());
+    }
+
+    let versions_dir = crate::utils::get_versions_dir()?;
+    let mut imported = Vec::new();
+
+    for go in &installations {
+        if versions_dir.join(go.version.to_dir_name()).exists() {
+            println!("Skipping Go {} at {} (already managed)", go.version, go.path.display());
+            continue;
+        }
+
+        match import_go(go, mode) {
+            Ok(path) => imported.push(path),
+            Err(e) => eprintln!("⚠️  Failed to import Go {} from {}: {}", go.version, go.path.display(), e),
+        }
+        println!();
+    }
+
+    println!("Imported {} of {} installation(s)", imported.len(), installations.len());
+
+    Ok(imported)
+}
+
+/// 获取 Go 模块缓存目录，依次使用 GOMODCACHE、GOPATH 的第一项和 ~/go
+pub fn get_gomodcache() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("GOMODCACHE").filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+
+    if let Some(gopath) = env::var_os("GOPATH").filter(|v| !v.is_empty()) {
+        if let Some(first) = env::split_paths(&gopath).next() {
+            return Some(first.join("pkg").join("mod"));
+        }
+    }
+
+    dirs::home_dir().map(|home| home.join("go").join("pkg").join("mod"))
+}
+
+/// 模块缓存中由 GOTOOLCHAIN 下载的工具链
+#[derive(Debug, Clone)]
+pub struct ModCacheToolchain {
+    pub module: ToolchainModule,
+    pub path: PathBuf,
+}
+
+impl ModCacheToolchain {
+    /// 是否为本机平台的工具链
+    pub fn is_host_platform(&self) -> bool {
+        let (os, arch) = crate::download::host_platform();
+        self.module.os == os && self.module.arch == arch
+    }
+}
+
+/// 读取 GOROOT 下 VERSION 文件中的版本号
+fn read_version_file(goroot: &Path) -> Option<GoVersion> {
+    let content = std::fs::read_to_string(goroot.join("VERSION")).ok()?;
+    GoVersion::parse(content.lines().next()?.trim()).ok()
+}
+
+/// 扫描模块缓存中的 golang.org/toolchain@v0.0.1-go1.x.y.<os>-<arch> 目录
+/// 只返回 VERSION 文件与模块版本一致的工具链
+pub fn find_modcache_toolchains() -> Vec<ModCacheToolchain> {
+    let dir = match get_gomodcache() {
+        Some(modcache) => modcache.join("golang.org"),
+        None => return Vec::new(),
+    };
+
+    let mut found: Vec<ModCacheToolchain> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let module = ToolchainModule::parse(name.strip_prefix("toolchain@")?)?;
+            let path = entry.path();
+            if read_version_file(&path)? != module.version {
+                return None;
+            }
+            Some(ModCacheToolchain { module, path })
+        })
+        .collect();
+
+    found.sort_by(|a, b| b.module.cmp(&a.module));
+    found
+}
+
+/// 模块缓存中的文件没有可执行权限，go 命令会在首次使用时补上，这里做同样的处理
+#[cfg(unix)]
+fn ensure_exec_bits(goroot: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut dirs = vec![goroot.join("bin")];
+    if let Ok(entries) = std::fs::read_dir(goroot.join("pkg").join("tool")) {
+        dirs.extend(entries.flatten().map(|e| e.path()));
+    }
+
+    for dir in dirs {
+        for entry in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let meta = entry.metadata()?;
+            if meta.is_file() && meta.permissions().mode() & 0o111 == 0 {
+                let mode = meta.permissions().mode() & 0o777 | 0o111;
+                std::fs::set_permissions(entry.path(), std::fs::Permissions::from_mode(mode))?;
+            }
+        }
+    }
+
     Ok(())
 }
 
+#[cfg(not(unix))]
+fn ensure_exec_bits(_goroot: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// 导入模块缓存中本机平台的工具链
+pub fn import_from_modcache(mode: ImportMode) -> Result<Vec<PathBuf>> {
+    let toolchains = find_modcache_toolchains();
+
+    if toolchains.is_empty() {
+        println!(
+            "No toolchains found in the module cache ({}).",
+            get_gomodcache().map(|p| p.display().to_string()).unwrap_or_default()
+        );
+        return Ok(Vec::new());
+    }
+
+    let versions_dir = crate::utils::get_versions_dir()?;
+    let mut imported = Vec::new();
+
+    for toolchain in &toolchains {
+        let version = &toolchain.module.version;
+
+        if !toolchain.is_host_platform() {
+            println!(
+                "Skipping Go {} (built for {}/{})",
+                version, toolchain.module.os, toolchain.module.arch
+            );
+            continue;
+        }
+        if versions_dir.join(version.to_dir_name()).exists() {
+            println!("Skipping Go {} (already managed)", version);
+            continue;
+        }
+
+        if let Err(e) = ensure_exec_bits(&toolchain.path) {
+            eprintln!("⚠️  Failed to make Go {} executable: {}", version, e);
+            continue;
+        }
+
+        let (source, evidence) = detect_go_source(&toolchain.path);
+        let go = SystemGo {
+            version: version.clone(),
+            path: toolchain.path.clone(),
+            bin_path: toolchain.path.join("bin").join(go_exe_name()),
+            source,
+            evidence,
+        };
+
+        match import_go(&go, mode) {
+            Ok(path) => imported.push(path),
+            Err(e) => eprintln!("⚠️  Failed to import Go {}: {}", version, e),
+        }
+        println!();
+    }
+
+    println!("Imported {} toolchain(s) from the module cache", imported.len());
+
+    if mode == ImportMode::Link && !imported.is_empty() {
+        println!("Linked toolchains disappear with 'go clean -modcache'; use '--mode copy' to keep them.");
+    }
+
+    Ok(imported)
+}
+
 /// 获取导入 Go 的提示信息
 pub fn get_import_hint() -> Option<String> {
     match detect_system_go() {
@@ -287,6 +940,181 @@ pub fn get_import_hint() -> Option<String> {
     }
 }
 
+/// Go 环境变量冲突
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvConflict {
+    /// GOROOT 指向的目录不是当前激活的版本
+    StaleGoroot {
+        value: String,
+        origin: &'static str,
+        expected: PathBuf,
+    },
+    /// GOTOOLCHAIN 允许 go 命令切换到其他工具链
+    ToolchainSwitch {
+        value: String,
+        origin: &'static str,
+    },
+}
+
+impl std::fmt::Display for EnvConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvConflict::StaleGoroot { value, origin, expected } => write!(
+                f,
+                "GOROOT={} (from {}) overrides the active version at {}",
+                value,
+                origin,
+                expected.display()
+            ),
+            EnvConflict::ToolchainSwitch { value, origin } => write!(
+                f,
+                "GOTOOLCHAIN={} (from {}) lets the go command run a different toolchain than the one selected by GoVM",
+                value,
+                origin
+            ),
+        }
+    }
+}
+
+/// 判断 GOTOOLCHAIN 的值是否允许 go 命令切换工具链
+/// 只有 "local" 会固定使用当前工具链；"auto"、"path"、"go1.x.y" 及 "+auto"/"+path" 形式都可能切换
+pub fn gotoolchain_allows_switch(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty() && value != "local"
+}
+
+/// 读取指定 go 可执行文件的 `go env` 变量
+pub fn query_go_env(go_bin: &Path, vars: &[&str]) -> Option<Vec<String>> {
+    // 在 GoVM 目录中运行并设置 GOTOOLCHAIN=local，避免用户当前目录的 go.mod
+    // 触发 go 命令下载并运行其他工具链
+    let work_dir = crate::utils::get_govm_dir().ok().filter(|d| d.is_dir()).unwrap_or_else(env::temp_dir);
+    let output = Command::new(go_bin)
+        .arg("env")
+        .args(vars)
+        .current_dir(work_dir)
+        .env("GOTOOLCHAIN", "local")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let values: Vec<String> = stdout.lines().map(|l| l.trim().to_string()).collect();
+
+    if values.len() != vars.len() {
+        return None;
+    }
+
+    Some(values)
+}
+
+/// 读取 go env 配置文件（GOENV 或 GOROOT/go.env）中的一个变量
+fn read_env_file(path: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(name, _)| name.trim() == key)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// 运行 GOROOT 中的 go version，返回去掉 "go version " 前缀的输出
+/// 例如 "devel go1.23-abc123 Tue Jan 2 ... linux/amd64"，用于显示自定义工具链
+pub fn go_version_string(goroot: &Path) -> Option<String> {
+    let output = Command::new(goroot.join("bin").join(go_exe_name()))
+        .arg("version")
+        .env("GOTOOLCHAIN", "local")
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().next()?.trim();
+    Some(line.strip_prefix("go version ").unwrap_or(line).to_string())
+}
+
+/// 比较两个路径是否指向同一目录（解析符号链接）
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// 检查 GOROOT 和 GOTOOLCHAIN 是否与 GoVM 当前激活的版本冲突
+pub fn detect_env_conflicts() -> Result<Vec<EnvConflict>> {
+    let mut conflicts = Vec::new();
+
+    let current_dir = crate::utils::get_current_dir()?;
+    if !current_dir.exists() {
+        return Ok(conflicts);
+    }
+
+    let expected = current_dir.canonicalize().unwrap_or_else(|_| current_dir.clone());
+
+    // 环境变量中的 GOROOT
+    let env_goroot = env::var("GOROOT").ok().filter(|v| !v.trim().is_empty());
+    if let Some(ref value) = env_goroot {
+        if !same_dir(Path::new(value), &expected) {
+            conflicts.push(EnvConflict::StaleGoroot {
+                value: value.clone(),
+                origin: "environment",
+                expected: expected.clone(),
+            });
+        }
+    }
+
+    // 环境变量中的 GOTOOLCHAIN
+    let env_toolchain = env::var("GOTOOLCHAIN").ok().filter(|v| !v.trim().is_empty());
+    if let Some(ref value) = env_toolchain {
+        if gotoolchain_allows_switch(value) {
+            conflicts.push(EnvConflict::ToolchainSwitch {
+                value: value.clone(),
+                origin: "environment",
+            });
+        }
+    }
+
+    // 当前版本的 go env（包含 go.env 和 'go env -w' 的设置）
+    #[cfg(windows)]
+    let go_bin = current_dir.join("bin").join("go.exe");
+    #[cfg(not(windows))]
+    let go_bin = current_dir.join("bin").join("go");
+
+    if let Some(values) = query_go_env(&go_bin, &["GOROOT", "GOENV"]) {
+        let (goroot, goenv) = (&values[0], &values[1]);
+        // go env 在 GOTOOLCHAIN=local 下运行，配置的值从 'go env -w' 的文件和 GOROOT/go.env 中读取
+        let toolchain = read_env_file(Path::new(goenv), "GOTOOLCHAIN")
+            .or_else(|| read_env_file(&Path::new(goroot).join("go.env"), "GOTOOLCHAIN"))
+            .unwrap_or_default();
+
+        if env_goroot.is_none() && !goroot.is_empty() && !same_dir(Path::new(goroot), &expected) {
+            conflicts.push(EnvConflict::StaleGoroot {
+                value: goroot.clone(),
+                origin: "go env",
+                expected: expected.clone(),
+            });
+        }
+
+        if env_toolchain.is_none() && gotoolchain_allows_switch(&toolchain) {
+            conflicts.push(EnvConflict::ToolchainSwitch {
+                value: toolchain,
+                origin: "go env",
+            });
+        }
+    }
+
+    Ok(conflicts)
+}
+
 /// 显示 PATH 冲突警告
 pub fn show_path_conflict_warning() {
     if let Ok(true) = is_system_go_first_in_path() {
@@ -294,8 +1122,148 @@ pub fn show_path_conflict_warning() {
         eprintln!("   GoVM's version switching will not take effect.");
         eprintln!();
         eprintln!("   To fix this, ensure GoVM's bin directory is BEFORE system Go in PATH:");
-        eprintln!("   - Windows: %LOCALAPPDATA%\\govm\\current\\bin");
-        eprintln!("   - Unix: $HOME/.govm/current/bin");
+        if let Ok(current) = crate::utils::get_current_dir() {
+            eprintln!("   {}", current.join("bin").display());
+        }
         eprintln!();
     }
+
+    let conflicts = detect_env_conflicts().unwrap_or_default();
+    if conflicts.is_empty() {
+        return;
+    }
+
+    eprintln!("⚠️  WARNING: Go environment variables conflict with GoVM.");
+    for conflict in &conflicts {
+        eprintln!("   - {}", conflict);
+    }
+    eprintln!();
+
+    if conflicts.iter().any(|c| matches!(c, EnvConflict::StaleGoroot { .. })) {
+        eprintln!("   GOROOT makes the go command use another installation's standard library.");
+        eprintln!("   Remove it from your shell profile ('unset GOROOT'), or 'go env -u GOROOT'.");
+    }
+    if conflicts.iter().any(|c| matches!(c, EnvConflict::ToolchainSwitch { .. })) {
+        eprintln!("   Since Go 1.21, GOTOOLCHAIN can silently download and run another toolchain.");
+        eprintln!("   Source GoVM's activation script or run 'govm env' to set GOTOOLCHAIN=local.");
+    }
+    eprintln!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_list_contains() {
+        let dpkg = "/.\n/usr/lib/go-1.22\n/usr/lib/go-1.22/bin/go\n";
+        let pacman = "%FILES%\nusr/lib/go/\nusr/lib/go/bin/go\n";
+        assert!(file_list_contains(dpkg, "/usr/lib/go-1.22/bin/go"));
+        assert!(file_list_contains(pacman, "/usr/lib/go/bin/go"));
+        assert!(!file_list_contains(dpkg, "/usr/lib/go-1.22/bin/gofmt"));
+    }
+
+    #[test]
+    fn test_detect_source_by_path() {
+        let nix = detect_source_by_path(Path::new("/nix/store/abc-go-1.22.3/share/go"));
+        assert_eq!(nix.map(|(s, _)| s), Some(GoSource::Nix));
+
+        let brew = detect_source_by_path(Path::new("/opt/homebrew/Cellar/go/1.22.3/libexec"));
+        assert_eq!(brew.map(|(s, _)| s), Some(GoSource::Homebrew));
+
+        assert!(detect_source_by_path(Path::new("/usr/local/go")).is_none());
+    }
+
+    #[test]
+    fn test_gotoolchain_allows_switch() {
+        assert!(!gotoolchain_allows_switch("local"));
+        assert!(!gotoolchain_allows_switch(""));
+        assert!(gotoolchain_allows_switch("auto"));
+        assert!(gotoolchain_allows_switch("path"));
+        assert!(gotoolchain_allows_switch("go1.22.3"));
+        assert!(gotoolchain_allows_switch("local+auto"));
+    }
+
+    #[test]
+    fn test_read_env_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("go.env");
+        std::fs::write(&path, "# comment\nGOPROXY=https://proxy.golang.org,direct\nGOTOOLCHAIN=auto\n").unwrap();
+        assert_eq!(read_env_file(&path, "GOTOOLCHAIN").as_deref(), Some("auto"));
+        assert_eq!(read_env_file(&path, "GOROOT"), None);
+        assert_eq!(read_env_file(&temp.path().join("missing"), "GOTOOLCHAIN"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_import_copy_follows_symlinked_dirs() {
+        // Debian 风格：/usr/lib/go-1.xx/src -> /usr/share/go-1.xx/src
+        let temp = tempfile::tempdir().unwrap();
+        let share = temp.path().join("share").join("src").join("fmt");
+        std::fs::create_dir_all(&share).unwrap();
+        std::fs::write(share.join("print.go"), "package fmt\n").unwrap();
+
+        let goroot = temp.path().join("lib");
+        std::fs::create_dir_all(goroot.join("bin")).unwrap();
+        std::fs::write(goroot.join("bin").join("go"), "").unwrap();
+        std::os::unix::fs::symlink(temp.path().join("share").join("src"), goroot.join("src")).unwrap();
+        std::os::unix::fs::symlink(temp.path().join("missing"), goroot.join("dangling")).unwrap();
+
+        let target = temp.path().join("versions").join("1.22.3");
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        import_to(&goroot, &target, ImportMode::Copy).unwrap();
+
+        assert!(!std::fs::symlink_metadata(target.join("src")).unwrap().file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(target.join("src/fmt/print.go")).unwrap(), "package fmt\n");
+        assert!(target.join("bin").join("go").is_file());
+        assert!(std::fs::symlink_metadata(target.join("dangling")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover_gos_dedupes_symlinked_goroots() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().join("root");
+        let home = temp.path().join("home");
+        let fake_go = |goroot: &Path, version: &str| {
+            std::fs::create_dir_all(goroot.join("bin")).unwrap();
+            let bin = goroot.join("bin").join("go");
+            std::fs::write(&bin, format!("#!/bin/sh\necho go version go{} linux/amd64\n", version)).unwrap();
+            std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+
+        fake_go(&root.join("usr/local/go"), "1.22.3");
+        fake_go(&root.join("usr/lib/go-1.21"), "1.21.5");
+        fake_go(&home.join("sdk/go1.20.14"), "1.20.14");
+        // 发行版的 /usr/lib/go -> go-1.21 与 go-1.21 是同一个安装
+        symlink(root.join("usr/lib/go-1.21"), root.join("usr/lib/go")).unwrap();
+        // 没有 bin/go 的目录不算
+        std::fs::create_dir_all(root.join("opt/go")).unwrap();
+
+        // PATH 中的 go 是指向 /usr/local/go/bin/go 的符号链接
+        let path_dir = temp.path().join("bin");
+        std::fs::create_dir_all(&path_dir).unwrap();
+        symlink(root.join("usr/local/go/bin/go"), path_dir.join("go")).unwrap();
+
+        // GoVM 自己的版本不参与扫描
+        let govm_dir = temp.path().join("govm");
+        fake_go(&govm_dir.join("versions/1.23.0"), "1.23.0");
+        let mut extra = well_known_goroots_under(&root, Some(&home));
+        extra.push(govm_dir.join("versions/1.23.0"));
+
+        let candidates = well_known_goroots_under(&root, Some(&home));
+        assert!(candidates.contains(&root.join("usr/lib/go")));
+        assert!(candidates.contains(&root.join("usr/lib/go-1.21")));
+        assert!(candidates.contains(&home.join("sdk/go1.20.14")));
+
+        let path = env::join_paths([&path_dir]).unwrap();
+        let found = discover_gos(&path, extra, &govm_dir);
+        let versions: Vec<String> = found.iter().map(|go| go.version.to_string()).collect();
+        assert_eq!(versions, ["1.22.3", "1.21.5", "1.20.14"]);
+        assert_eq!(found[0].path, root.join("usr/local/go").canonicalize().unwrap());
+        // 先出现的符号链接路径保留下来，go-1.21 本身被当作重复项跳过
+        assert_eq!(found[1].path, root.join("usr/lib/go"));
+    }
 }
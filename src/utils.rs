@@ -1,12 +1,65 @@
 use anyhow::{Result, anyhow};
-use dirs;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// 目录布局
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+    /// 所有数据放在同一个根目录下：便携目录、GOVM_HOME、~/.govm 或 %LOCALAPPDATA%/govm
+    Home(PathBuf),
+    /// 遵循 XDG 规范：数据、缓存和配置分别放在 XDG_DATA_HOME、XDG_CACHE_HOME 和 XDG_CONFIG_HOME 下
+    Xdg {
+        data: PathBuf,
+        cache: PathBuf,
+        config: PathBuf,
+    },
+}
+
+/// 环境变量中的目录，未设置或为空时返回 None
+fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// XDG 目录，未设置时使用规范中的默认值
+fn xdg_dir(var: &str, default: &str) -> Option<PathBuf> {
+    env_dir(var).or_else(|| dirs::home_dir().map(|h| h.join(default)))
+}
+
+/// 便携模式的标记文件，放在 govm 可执行文件旁边
+pub const PORTABLE_MARKER: &str = "govm.portable";
+
+/// 便携模式的根目录：可执行文件所在目录中有标记文件时返回该目录
+pub fn portable_root() -> Option<PathBuf> {
+    let exe = env::current_exe().ok()?.canonicalize().ok()?;
+    let dir = exe.parent()?;
+    dir.join(PORTABLE_MARKER).is_file().then(|| dir.to_path_buf())
+}
+
+/// 确定目录布局
+/// 1. 便携模式（可执行文件旁有 govm.portable）时所有数据都放在可执行文件所在目录
+/// 2. 设置了 GOVM_HOME 时所有数据都放在该目录下
+/// 3. Unix 上设置了 GOVM_XDG=1，或 $XDG_DATA_HOME/govm 已存在时使用 XDG 布局
+/// 4. 否则使用 ~/.govm（Windows 为 %LOCALAPPDATA%/govm）
+pub fn layout() -> Result<Layout> {
+    if let Some(root) = portable_root() {
+        return Ok(Layout::Home(root));
+    }
+    if let Some(home) = env_dir("GOVM_HOME") {
+        return Ok(Layout::Home(home));
+    }
+
+    if cfg!(unix) {
+        let data = xdg_dir("XDG_DATA_HOME", ".local/share").map(|d| d.join("govm"));
+        let wanted = env::var("GOVM_XDG").map(|v| matches!(v.as_str(), "1" | "true" | "yes")).unwrap_or(false);
+        if let Some(data) = data.filter(|d| wanted || d.is_dir()) {
+            let cache = xdg_dir("XDG_CACHE_HOME", ".cache").map(|d| d.join("govm"));
+            let config = xdg_dir("XDG_CONFIG_HOME", ".config").map(|d| d.join("govm"));
+            if let (Some(cache), Some(config)) = (cache, config) {
+                return Ok(Layout::Xdg { data, cache, config });
+            }
+        }
+    }
 
-/// 获取 GoVM 的根目录
-/// Windows: %LOCALAPPDATA%/govm
-/// Unix: ~/.govm
-pub fn get_govm_dir() -> Result<PathBuf> {
     let dir = if cfg!(windows) {
         dirs::data_local_dir()
             .ok_or_else(|| anyhow!("Failed to get local data directory"))?
@@ -17,7 +70,24 @@ pub fn get_govm_dir() -> Result<PathBuf> {
             .join(".govm")
     };
 
-    Ok(dir)
+    Ok(Layout::Home(dir))
+}
+
+/// 获取 GoVM 的根目录（存放版本、current 和别名等数据）
+/// 默认 Windows: %LOCALAPPDATA%/govm，Unix: ~/.govm，可以通过 GOVM_HOME 或 XDG 布局改变
+pub fn get_govm_dir() -> Result<PathBuf> {
+    Ok(match layout()? {
+        Layout::Home(dir) => dir,
+        Layout::Xdg { data, .. } => data,
+    })
+}
+
+/// 获取存放用户配置文件的目录
+pub fn get_config_dir() -> Result<PathBuf> {
+    Ok(match layout()? {
+        Layout::Home(dir) => dir,
+        Layout::Xdg { config, .. } => config,
+    })
 }
 
 /// 获取存放所有版本的目录
@@ -29,7 +99,10 @@ pub fn get_versions_dir() -> Result<PathBuf> {
 
 /// 获取缓存目录
 pub fn get_cache_dir() -> Result<PathBuf> {
-    let dir = get_govm_dir()?.join("cache");
+    let dir = match layout()? {
+        Layout::Home(dir) => dir.join("cache"),
+        Layout::Xdg { cache, .. } => cache,
+    };
     std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
@@ -55,8 +128,13 @@ pub fn clean_cache() -> Result<()> {
 }
 
 /// 获取系统的临时目录
+/// Unix 上 /tmp 由所有用户共用，目录名中带上用户名，避免其他用户创建的目录不可写
 pub fn get_temp_dir() -> Result<PathBuf> {
-    let dir = env::temp_dir().join("govm");
+    let name = match env::var("USER") {
+        Ok(user) if cfg!(unix) && !user.is_empty() => format!("govm-{}", user),
+        _ => "govm".to_string(),
+    };
+    let dir = env::temp_dir().join(name);
     std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
@@ -74,3 +152,103 @@ pub fn format_size(size: u64) -> String {
 
     format!("{:.2} {}", size, UNITS[unit_index])
 }
+
+/// 计算目录占用的空间，不跟随符号链接
+pub fn dir_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// 解析时长，支持 s/m/h/d/w 后缀，例如 "30d"、"12h"
+pub fn parse_duration(value: &str) -> Result<std::time::Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration: {}. Expected format: 30d", value))?;
+
+    let seconds = match unit.trim().to_lowercase().as_str() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "" | "d" => 86400,
+        "w" => 7 * 86400,
+        _ => return Err(anyhow!("Invalid duration unit: {}. Use s, m, h, d or w", unit)),
+    };
+
+    let total = number
+        .checked_mul(seconds)
+        .ok_or_else(|| anyhow!("Duration too large: {}", value))?;
+    Ok(std::time::Duration::from_secs(total))
+}
+
+/// 解析文件大小，支持 B/KB/MB/GB/TB（按 1024 换算），例如 "2GB"、"500M"
+pub fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid size: {}. Expected format: 2GB", value))?;
+
+    let multiplier: u64 = match unit.trim().to_uppercase().trim_end_matches("IB").trim_end_matches('B') {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        "T" => 1024 * 1024 * 1024 * 1024,
+        _ => return Err(anyhow!("Invalid size unit: {}. Use B, KB, MB, GB or TB", unit)),
+    };
+
+    Ok((number * multiplier as f64) as u64)
+}
+
+/// 格式化时长，只保留最大的单位，例如 "3d"、"5h"
+pub fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+
+    if secs >= 86400 {
+        format!("{}d", secs / 86400)
+    } else if secs >= 3600 {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30d").unwrap(), Duration::from_secs(30 * 86400));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert_eq!(parse_duration("2w").unwrap(), Duration::from_secs(14 * 86400));
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("2GB").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_size("500M").unwrap(), 500 * 1024 * 1024);
+        assert_eq!(parse_size("1.5KiB").unwrap(), 1536);
+        assert_eq!(parse_size("42").unwrap(), 42);
+        assert!(parse_size("10XB").is_err());
+    }
+}
@@ -42,11 +42,7 @@ impl GoVersion {
         let patch = parts[2].parse::<u32>()
             .map_err(|_| anyhow!("Invalid patch version: {}", parts[2]))?;
 
-        Ok(Self {
-            major,
-            minor,
-            patch,
-        })
+        Ok(Self::new(major, minor, patch))
     }
 
     /// 获取下载用的版本字符串 (例如: go1.21.5)
@@ -74,10 +70,71 @@ impl FromStr for GoVersion {
     }
 }
 
+/// 已安装的工具链：官方发行版本，或自定义名称的工具链（例如 tip、1.22.3-boringcrypto、myfork）
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Toolchain {
+    Release(GoVersion),
+    Named(String),
+}
+
+impl Toolchain {
+    /// 解析版本号或工具链名称
+    pub fn parse(value: &str) -> Result<Self> {
+        if let Ok(version) = GoVersion::parse(value) {
+            return Ok(Toolchain::Release(version));
+        }
+        if is_valid_toolchain_name(value) {
+            return Ok(Toolchain::Named(value.to_string()));
+        }
+        Err(anyhow!(
+            "Invalid version or toolchain name: {}. Use a version like 1.21.5 or a name like tip",
+            value
+        ))
+    }
+
+    /// versions 目录下的目录名
+    pub fn dir_name(&self) -> String {
+        match self {
+            Toolchain::Release(version) => version.to_dir_name(),
+            Toolchain::Named(name) => name.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Toolchain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Toolchain::Release(version) => write!(f, "{}", version),
+            Toolchain::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// 自定义工具链名称只能包含字母、数字和 . _ - +，以字母或数字开头
+/// 只有数字和点的名称（例如 1.22）容易与版本号混淆，不允许使用
+pub fn is_valid_toolchain_name(name: &str) -> bool {
+    let valid_chars = name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+'));
+    let starts_ok = name.chars().next().is_some_and(|c| c.is_ascii_alphanumeric());
+    let numeric = name.chars().all(|c| c.is_ascii_digit() || c == '.');
+    let versioned = GoVersion::parse(name).is_ok();
+
+    valid_chars && starts_ok && !numeric && !versioned
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_toolchain() {
+        assert_eq!(Toolchain::parse("go1.22.3").unwrap(), Toolchain::Release(GoVersion::new(1, 22, 3)));
+        assert_eq!(Toolchain::parse("1.22.3-boringcrypto").unwrap(), Toolchain::Named("1.22.3-boringcrypto".to_string()));
+        assert_eq!(Toolchain::parse("tip").unwrap().dir_name(), "tip");
+        assert!(Toolchain::parse("1.22").is_err());
+        assert!(Toolchain::parse(".hidden").is_err());
+        assert!(Toolchain::parse("my/fork").is_err());
+    }
+
     #[test]
     fn test_parse_version() {
         let v = GoVersion::parse("1.21.5").unwrap();